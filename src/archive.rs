@@ -0,0 +1,429 @@
+//! Archival format with adaptive arithmetic coding, which trades speed and random access for size.
+//!
+//! Stores the same zigzag-encoded changes as [`encode_binary()`](crate::encode_binary), but instead of varints each
+//! change is coded as the number of its significant bits followed by the bits themselves. The number of bits
+//! and the highest bits after the leading one are coded with adaptive probability models, so the typical
+//! changes of a particular polyline take less space than rare ones. The rest of the bits are nearly random
+//! and are stored as is. The arithmetic coder is the range coder used by LZMA.
+//!
+//! The data starts with the number of points as a 7-bit varint, followed by the range-coded stream.
+
+use crate::{Scale, scale_point, varint64_decode7, varint64_encode7, zigzag_decode, zigzag_encode};
+
+/// Probabilities are stored as 11-bit fixed-point numbers.
+const PROBABILITY_BITS: u32 = 11;
+/// Probability 0.5 for the models without any statistics yet.
+const PROBABILITY_INIT: u16 = 1 << (PROBABILITY_BITS - 1);
+/// Adaptation speed: each coded bit moves the probability by 1/32 of the distance to the observed value.
+const MOVE_BITS: u32 = 5;
+/// The range is normalized to keep at least 24 significant bits.
+const TOP: u32 = 1 << 24;
+
+/// Up to 32 significant bits of a zigzag-encoded `i32` need 6 bits.
+const LENGTH_BITS: u32 = 6;
+/// Number of bits after the leading one that are coded with adaptive models.
+const MANTISSA_MODEL_BITS: u32 = 3;
+
+/// Adaptive probability models, separate for latitude and longitude.
+struct Models {
+    /// Number of significant bits of the zigzag-encoded change, in the context of the previous one.
+    length: [[[u16; 1 << LENGTH_BITS]; 33]; 2],
+    /// Highest bits after the leading one, in the context of the number of significant bits.
+    mantissa: [[[u16; 1 << MANTISSA_MODEL_BITS]; 33]; 2],
+    /// Number of significant bits of the previous change.
+    prev_length: [usize; 2],
+}
+
+impl Models {
+    fn new() -> Box<Self> {
+        Box::new(Models {
+            length: [[[PROBABILITY_INIT; 1 << LENGTH_BITS]; 33]; 2],
+            mantissa: [[[PROBABILITY_INIT; 1 << MANTISSA_MODEL_BITS]; 33]; 2],
+            prev_length: [0; 2],
+        })
+    }
+
+    fn encode(&mut self, encoder: &mut RangeEncoder, coordinate: usize, value: u32) {
+        let length = (32 - value.leading_zeros()) as usize;
+        let context = self.prev_length[coordinate];
+        encoder.encode_tree(
+            &mut self.length[coordinate][context],
+            LENGTH_BITS,
+            length as u32,
+        );
+        self.prev_length[coordinate] = length;
+
+        // The leading one is implied by the length
+        let extra_bits = length.saturating_sub(1) as u32;
+        let model_bits = extra_bits.min(MANTISSA_MODEL_BITS);
+        let direct_bits = extra_bits - model_bits;
+        let high = (value >> direct_bits) & ((1 << model_bits) - 1);
+        encoder.encode_tree(&mut self.mantissa[coordinate][length], model_bits, high);
+        encoder.encode_direct(value, direct_bits);
+    }
+
+    fn decode(&mut self, decoder: &mut RangeDecoder, coordinate: usize) -> Option<u32> {
+        let context = self.prev_length[coordinate];
+        let length = decoder.decode_tree(&mut self.length[coordinate][context], LENGTH_BITS)?;
+        if length > 32 {
+            return None;
+        }
+        let length = length as usize;
+        self.prev_length[coordinate] = length;
+        if length == 0 {
+            return Some(0);
+        }
+
+        let extra_bits = length as u32 - 1;
+        let model_bits = extra_bits.min(MANTISSA_MODEL_BITS);
+        let direct_bits = extra_bits - model_bits;
+        let high = decoder.decode_tree(&mut self.mantissa[coordinate][length], model_bits)?;
+        let low = decoder.decode_direct(direct_bits)?;
+        Some((1 << extra_bits) | (high << direct_bits) | low)
+    }
+}
+
+struct RangeEncoder {
+    output: Vec<u8>,
+    low: u64,
+    range: u32,
+    /// The last byte that is not written yet, as it may still be changed by a carry.
+    cache: u8,
+    /// Number of pending bytes: the cache followed by `0xFF` bytes.
+    cache_size: u64,
+}
+
+impl RangeEncoder {
+    fn new(output: Vec<u8>) -> Self {
+        RangeEncoder {
+            output,
+            low: 0,
+            range: u32::MAX,
+            cache: 0,
+            cache_size: 1,
+        }
+    }
+
+    fn encode_bit(&mut self, probability: &mut u16, bit: u32) {
+        let bound = (self.range >> PROBABILITY_BITS) * *probability as u32;
+        if bit == 0 {
+            self.range = bound;
+            *probability += ((1 << PROBABILITY_BITS) - *probability) >> MOVE_BITS;
+        } else {
+            self.low += bound as u64;
+            self.range -= bound;
+            *probability -= *probability >> MOVE_BITS;
+        }
+        self.normalize();
+    }
+
+    /// Encodes `bits` lowest bits of `value` from the most significant one, each with its own model.
+    fn encode_tree(&mut self, probabilities: &mut [u16], bits: u32, value: u32) {
+        let mut index = 1;
+        for i in (0..bits).rev() {
+            let bit = (value >> i) & 1;
+            self.encode_bit(&mut probabilities[index], bit);
+            index = (index << 1) | bit as usize;
+        }
+    }
+
+    /// Encodes `bits` lowest bits of `value` with probability 0.5.
+    fn encode_direct(&mut self, value: u32, bits: u32) {
+        for i in (0..bits).rev() {
+            self.range >>= 1;
+            if (value >> i) & 1 != 0 {
+                self.low += self.range as u64;
+            }
+            self.normalize();
+        }
+    }
+
+    fn normalize(&mut self) {
+        while self.range < TOP {
+            self.range <<= 8;
+            self.shift_low();
+        }
+    }
+
+    fn shift_low(&mut self) {
+        if self.low < 0xFF00_0000 || self.low >= 1 << 32 {
+            let carry = (self.low >> 32) as u8;
+            let mut byte = self.cache;
+            while self.cache_size > 0 {
+                self.output.push(byte.wrapping_add(carry));
+                byte = 0xFF;
+                self.cache_size -= 1;
+            }
+            self.cache = (self.low >> 24) as u8;
+        }
+        self.cache_size += 1;
+        self.low = (self.low & 0x00FF_FFFF) << 8;
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        for _ in 0..5 {
+            self.shift_low();
+        }
+        self.output
+    }
+}
+
+struct RangeDecoder<'a> {
+    input: &'a [u8],
+    range: u32,
+    code: u32,
+}
+
+impl<'a> RangeDecoder<'a> {
+    fn new(mut input: &'a [u8]) -> Self {
+        let mut code = 0;
+        // The first byte is always zero, as the encoder starts with an empty cache
+        for _ in 0..5 {
+            let Some((&byte, rest)) = input.split_first() else {
+                break;
+            };
+            code = (code << 8) | byte as u32;
+            input = rest;
+        }
+        RangeDecoder {
+            input,
+            range: u32::MAX,
+            code,
+        }
+    }
+
+    fn decode_bit(&mut self, probability: &mut u16) -> Option<u32> {
+        let bound = (self.range >> PROBABILITY_BITS) * *probability as u32;
+        let bit = if self.code < bound {
+            self.range = bound;
+            *probability += ((1 << PROBABILITY_BITS) - *probability) >> MOVE_BITS;
+            0
+        } else {
+            self.code -= bound;
+            self.range -= bound;
+            *probability -= *probability >> MOVE_BITS;
+            1
+        };
+        self.normalize()?;
+        Some(bit)
+    }
+
+    fn decode_tree(&mut self, probabilities: &mut [u16], bits: u32) -> Option<u32> {
+        let mut index = 1;
+        for _ in 0..bits {
+            index = (index << 1) | self.decode_bit(&mut probabilities[index])? as usize;
+        }
+        Some(index as u32 - (1 << bits))
+    }
+
+    fn decode_direct(&mut self, bits: u32) -> Option<u32> {
+        let mut result = 0;
+        for _ in 0..bits {
+            self.range >>= 1;
+            let bit = (self.code >= self.range) as u32;
+            if bit != 0 {
+                self.code -= self.range;
+            }
+            result = (result << 1) | bit;
+            self.normalize()?;
+        }
+        Some(result)
+    }
+
+    /// Reads the next byte if needed, failing when the input ends, which never happens for valid data.
+    fn normalize(&mut self) -> Option<()> {
+        while self.range < TOP {
+            let (&byte, rest) = self.input.split_first()?;
+            self.input = rest;
+            self.range <<= 8;
+            self.code = (self.code << 8) | byte as u32;
+        }
+        Some(())
+    }
+}
+
+/// Iterator over geographic coordinates decoded from archival data, created by [`decode_archive()`].
+///
+/// Stops on the first malformed point, just like [`BinaryPolylineIter`](crate::BinaryPolylineIter).
+pub struct ArchiveIter<'a> {
+    decoder: RangeDecoder<'a>,
+    models: Box<Models>,
+    scale: f64,
+    /// Number of points left to decode.
+    remaining: u64,
+    /// Last processed latitude, multiplied by the scale.
+    lat: i32,
+    /// Last processed longitude, multiplied by the scale.
+    lon: i32,
+}
+
+impl<'a> ArchiveIter<'a> {
+    /// Creates a new iterator over points decoded from archival data.
+    pub fn new(precision: u8, data: &'a [u8]) -> Self {
+        assert!(precision <= 7, "i32 can hold up to 180 * 10^7");
+        let mut data = data;
+        let remaining = varint64_decode7(&mut data).unwrap_or(0);
+        ArchiveIter {
+            decoder: RangeDecoder::new(data),
+            models: Models::new(),
+            scale: Scale::from_precision(precision).factor(),
+            remaining,
+            lat: 0,
+            lon: 0,
+        }
+    }
+
+    /// Number of points left to decode, as stored in the data. O(1) unlike other iterators of this crate.
+    pub fn len(&self) -> usize {
+        self.remaining as usize
+    }
+
+    /// Checks if there are no points left to decode.
+    pub fn is_empty(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+impl Iterator for ArchiveIter<'_> {
+    type Item = (f64, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let changes = self
+            .models
+            .decode(&mut self.decoder, 0)
+            .zip(self.models.decode(&mut self.decoder, 1));
+        let Some((lat_change, lon_change)) = changes else {
+            self.remaining = 0;
+            return None;
+        };
+        self.remaining -= 1;
+        // Wrapping to avoid overflow panics on malformed input
+        self.lat = self.lat.wrapping_add(zigzag_decode(lat_change));
+        self.lon = self.lon.wrapping_add(zigzag_decode(lon_change));
+        Some((self.lat as f64 / self.scale, self.lon as f64 / self.scale))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // The number of points may be wrong only for malformed input.
+        (0, Some(self.len()))
+    }
+}
+
+/// Decodes points from archival data created by [`encode_archive()`].
+///
+/// ```
+/// let points = [(55.58513, 12.99958), (55.61461, 13.04627)];
+/// let archive = polyline_iter::encode_archive(5, points);
+/// assert_eq!(polyline_iter::decode_archive(5, &archive).collect::<Vec<_>>(), points);
+/// ```
+pub fn decode_archive(precision: u8, data: &[u8]) -> ArchiveIter<'_> {
+    ArchiveIter::new(precision, data)
+}
+
+/// Encodes a sequence of points into the archival format, which is the most compact format of this crate,
+/// but also the slowest one, as changes are compressed with adaptive arithmetic coding.
+///
+/// The stored changes are exactly the same as in [`encode_binary()`](crate::encode_binary),
+/// so decoded points are the same as well. For example, the medium and long polylines from the benchmarks
+/// take 1408 and 20004 bytes with `encode_binary()`, and 1306 and 16640 bytes with this function.
+/// The longer the polyline, the better the models adapt to it.
+///
+/// ```
+/// let polyline = "angrIk~inAgwDybH_|D_{KeoEwtLozFo`Gre@tcA";
+/// let points: Vec<_> = polyline_iter::decode(5, polyline).collect();
+/// let archive = polyline_iter::encode_archive(5, points.iter().copied());
+/// assert_eq!(polyline_iter::decode_archive(5, &archive).collect::<Vec<_>>(), points);
+/// ```
+pub fn encode_archive(precision: u8, points: impl IntoIterator<Item = (f64, f64)>) -> Vec<u8> {
+    assert!(precision <= 7, "i32 can hold up to 180 * 10^7");
+    let scale = Scale::from_precision(precision).factor();
+
+    // The number of points is written before the stream, so changes are collected first
+    let mut changes = Vec::with_capacity(16);
+    let mut prev = (0, 0);
+    for point in points {
+        let point = scale_point(point, scale);
+        let lat_change = point.0.wrapping_sub(prev.0);
+        let lon_change = point.1.wrapping_sub(prev.1);
+        changes.push((zigzag_encode(lat_change), zigzag_encode(lon_change)));
+        prev = point;
+    }
+
+    let mut header = Vec::with_capacity(changes.len() + 8);
+    varint64_encode7(changes.len() as u64, &mut header);
+    if changes.is_empty() {
+        return header;
+    }
+
+    let mut encoder = RangeEncoder::new(header);
+    let mut models = Models::new();
+    for (lat_change, lon_change) in changes {
+        models.encode(&mut encoder, 0, lat_change);
+        models.encode(&mut encoder, 1, lon_change);
+    }
+    encoder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::{LONG_POLYLINE6, MEDIUM_POLYLINE6};
+    use crate::{decode, decode_binary, encode_binary};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn same_points_as_binary() {
+        for polyline in [MEDIUM_POLYLINE6, LONG_POLYLINE6] {
+            let points: Vec<_> = decode(6, polyline).collect();
+            let archive = encode_archive(6, points.iter().copied());
+            let iter = decode_archive(6, &archive);
+            assert_eq!(iter.len(), points.len());
+            assert_eq!(iter.collect::<Vec<_>>(), points);
+        }
+
+        // Changes that aren't exact multiples of the scale are rounded exactly as in `encode_binary()`
+        let points = [
+            (0.0, 0.0),
+            (90.0, 180.0),
+            (-90.0, -180.0),
+            (0.123456789, -0.987654321),
+            (i32::MAX as f64 / 1e7, i32::MIN as f64 / 1e7),
+        ];
+        assert_eq!(
+            decode_archive(7, &encode_archive(7, points)).collect::<Vec<_>>(),
+            decode_binary(7, &encode_binary(7, points)).collect::<Vec<_>>()
+        );
+        for precision in [0, 5] {
+            assert_eq!(
+                decode_archive(precision, &encode_archive(precision, points)).collect::<Vec<_>>(),
+                decode_binary(precision, &encode_binary(precision, points)).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(encode_archive(5, []), [0]);
+        assert!(decode_archive(5, &[0]).is_empty());
+        assert_eq!(decode_archive(5, &[0]).next(), None);
+        assert_eq!(decode_archive(5, &[]).next(), None);
+    }
+
+    #[test]
+    fn broken_data() {
+        let points: Vec<_> = decode(6, MEDIUM_POLYLINE6).collect();
+        let archive = encode_archive(6, points.iter().copied());
+
+        // Truncated stream stops decoding instead of yielding garbage forever
+        let truncated = &archive[..archive.len() / 2];
+        let decoded: Vec<_> = decode_archive(6, truncated).collect();
+        assert!(decoded.len() < points.len());
+        assert_eq!(decoded[..decoded.len() / 2], points[..decoded.len() / 2]);
+
+        // Huge point count with no data
+        assert_eq!(decode_archive(6, &[0xFF, 0xFF, 0xFF, 0x0F]).next(), None);
+    }
+}
@@ -0,0 +1,255 @@
+//! URL-safe transport for the binary format: [`encode_binary()`](crate::encode_binary) output wrapped into
+//! unpadded base64url (RFC 4648 §5), which is decoded lazily without materializing the bytes.
+
+use crate::{Scale, bitwise_split, encode_binary, zigzag_decode};
+
+/// URL-safe base64 alphabet, also used by [HERE Flexible Polyline](crate::flexible).
+pub(crate) const ENCODING_TABLE: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Reverse of [`ENCODING_TABLE`].
+pub(crate) const DECODING_TABLE: [u8; 256] = decoding_table(ENCODING_TABLE);
+
+/// Builds the reverse of a 64-character alphabet, with `0xFF` for bytes outside of the alphabet.
+pub(crate) const fn decoding_table(alphabet: &[u8; 64]) -> [u8; 256] {
+    let mut table = [0xFF; 256];
+    let mut i = 0;
+    while i < alphabet.len() {
+        table[alphabet[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+/// Lazy base64url decoder, yielding bytes until the end of the string or the first invalid character.
+#[derive(Clone)]
+struct Base64UrlBytes<'a> {
+    chars: &'a [u8],
+    /// Decoded bits that are not yielded yet, in the lowest `bits_len` bits.
+    bits: u32,
+    bits_len: u32,
+}
+
+impl Iterator for Base64UrlBytes<'_> {
+    type Item = u8;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<u8> {
+        while self.bits_len < 8 {
+            let (&char, rest) = self.chars.split_first()?;
+            let value = DECODING_TABLE[char as usize];
+            if value == 0xFF {
+                self.chars = &[];
+                return None;
+            }
+            self.chars = rest;
+            self.bits = (self.bits << 6) | value as u32;
+            self.bits_len += 6;
+        }
+        self.bits_len -= 8;
+        Some((self.bits >> self.bits_len) as u8)
+    }
+}
+
+/// Decodes the whole string, or returns `None` if it has characters outside of the alphabet
+/// or a trailing character that doesn't complete a byte.
+#[cfg(feature = "serde")]
+pub(crate) fn base64url_decode(polyline: &str) -> Option<Vec<u8>> {
+    let valid = polyline
+        .bytes()
+        .all(|char| DECODING_TABLE[char as usize] != 0xFF);
+    if !valid || polyline.len() % 4 == 1 {
+        return None;
+    }
+    let bytes = Base64UrlBytes {
+        chars: polyline.as_bytes(),
+        bits: 0,
+        bits_len: 0,
+    };
+    Some(bytes.collect())
+}
+
+/// Iterator over geographic coordinates decoded from a base64url string created by [`encode_binary_base64url()`].
+///
+/// Works exactly as [`BinaryPolylineIter`](crate::BinaryPolylineIter), but reads bytes directly from the string.
+/// Stops on the first malformed point or the first character outside of the base64url alphabet.
+pub struct BinaryPolylineIterBase64Url<'a> {
+    bytes: Base64UrlBytes<'a>,
+    scale: f64,
+    /// Last processed latitude, multiplied by the scale.
+    lat: i32,
+    /// Last processed longitude, multiplied by the scale.
+    lon: i32,
+}
+
+impl<'a> BinaryPolylineIterBase64Url<'a> {
+    /// Creates a new iterator over points decoded from a base64url-encoded binary polyline.
+    #[inline(always)]
+    pub fn new(precision: u8, polyline: &'a str) -> Self {
+        assert!(precision <= 7, "i32 can hold up to 180 * 10^7");
+        BinaryPolylineIterBase64Url {
+            bytes: Base64UrlBytes {
+                chars: polyline.as_bytes(),
+                bits: 0,
+                bits_len: 0,
+            },
+            scale: Scale::from_precision(precision).factor(),
+            lat: 0,
+            lon: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn varint_decode(&mut self) -> Option<u64> {
+        let mut result = 0;
+        // 64 bits are stored in at most 10 bytes by 7 bits each, so the 10th byte can only be 0 or 1.
+        for i in 0..10 {
+            let chunk = self.bytes.next()? as u64;
+            if i == 9 && chunk > 1 {
+                return None;
+            }
+            result |= (chunk & 0x7f) << (i * 7); // no shift overflow as i < 10
+            if chunk & 0x80 == 0 {
+                return Some(result);
+            }
+        }
+        None
+    }
+
+    /// O(n) operation to count the number of points in the polyline without consuming the iterator.
+    pub fn len(&self) -> usize {
+        self.bytes.clone().filter(|&byte| byte & 0x80 == 0).count()
+    }
+
+    /// Checks if the polyline contains no points.
+    pub fn is_empty(&self) -> bool {
+        !self.bytes.clone().any(|byte| byte & 0x80 == 0)
+    }
+}
+
+impl Iterator for BinaryPolylineIterBase64Url<'_> {
+    type Item = (f64, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (lat_change, lon_change) = bitwise_split(self.varint_decode()?);
+        // Wrapping to avoid overflow panics on malformed input
+        self.lat = self.lat.wrapping_add(zigzag_decode(lat_change));
+        self.lon = self.lon.wrapping_add(zigzag_decode(lon_change));
+        Some((self.lat as f64 / self.scale, self.lon as f64 / self.scale))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each 4 chars hold 3 bytes, and each point takes from 1 to 10 bytes.
+        let len = self.bytes.chars.len() * 3 / 4 + 1;
+        (len / 10, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+/// Decodes points from a base64url string created by [`encode_binary_base64url()`].
+#[inline(always)]
+pub fn decode_binary_base64url(precision: u8, polyline: &str) -> BinaryPolylineIterBase64Url<'_> {
+    BinaryPolylineIterBase64Url::new(precision, polyline)
+}
+
+/// Encodes points with [`encode_binary()`] and wraps the result into unpadded base64url,
+/// so it can be used in URLs and JSON strings.
+///
+/// As base64 stores only 6 bits per char, the result is only slightly shorter than [`encode()`](crate::encode),
+/// which stores 5 bits per char, e.g. 1878 instead of 1901 chars and 26672 instead of 26817 chars
+/// for the medium and long polylines from the benchmarks.
+///
+/// ```
+/// let points = [(55.58513, 12.99958), (55.61461, 13.04627)];
+/// let polyline = polyline_iter::encode_binary_base64url(5, points);
+/// assert_eq!(polyline, "pPmv5LyNE8jV1Ek");
+/// assert_eq!(polyline_iter::decode_binary_base64url(5, &polyline).collect::<Vec<_>>(), points);
+/// ```
+pub fn encode_binary_base64url(
+    precision: u8,
+    points: impl IntoIterator<Item = (f64, f64)>,
+) -> String {
+    let bytes = encode_binary(precision, points);
+    let mut result = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, &byte)| {
+            bits | (byte as u32) << (16 - 8 * i)
+        });
+        // 1 byte takes 2 chars, 2 bytes take 3 chars and 3 bytes take 4 chars
+        for i in 0..=chunk.len() {
+            result.push(ENCODING_TABLE[(bits >> (18 - 6 * i)) as usize & 0x3F] as char);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, decode_binary};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn rfc4648_vectors() {
+        let bytes = |s: &str| {
+            Base64UrlBytes {
+                chars: s.as_bytes(),
+                bits: 0,
+                bits_len: 0,
+            }
+            .collect::<Vec<_>>()
+        };
+        assert_eq!(bytes(""), b"");
+        assert_eq!(bytes("Zg"), b"f");
+        assert_eq!(bytes("Zm8"), b"fo");
+        assert_eq!(bytes("Zm9v"), b"foo");
+        assert_eq!(bytes("Zm9vYg"), b"foob");
+        assert_eq!(bytes("Zm9vYmE"), b"fooba");
+        assert_eq!(bytes("Zm9vYmFy"), b"foobar");
+        // URL-safe alphabet
+        assert_eq!(bytes("-_8"), [0xFB, 0xFF]);
+        // Stops at padding and invalid chars
+        assert_eq!(bytes("Zg=="), b"f");
+        assert_eq!(bytes("Zm9v+mFy"), b"foo");
+    }
+
+    #[test]
+    fn same_as_binary() {
+        let points: Vec<_> = decode(5, "angrIk~inAgwDybH_|D_{KeoEwtLozFo`Gre@tcA").collect();
+        // Every possible tail length
+        for len in 0..=points.len() {
+            let polyline = encode_binary_base64url(5, points[..len].iter().copied());
+            assert!(
+                polyline
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_')
+            );
+            let iter = decode_binary_base64url(5, &polyline);
+            assert_eq!(iter.len(), len);
+            assert_eq!(iter.is_empty(), len == 0);
+            assert_eq!(iter.collect::<Vec<_>>(), points[..len]);
+        }
+
+        let extremes = [(i32::MAX as f64, i32::MIN as f64), (0.0, -1.0)];
+        let polyline = encode_binary_base64url(0, extremes);
+        assert_eq!(
+            decode_binary_base64url(0, &polyline).collect::<Vec<_>>(),
+            decode_binary(0, &crate::encode_binary(0, extremes)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn broken_string() {
+        let points = [(55.58513, 12.99958), (55.61461, 13.04627)];
+        let polyline = encode_binary_base64url(5, points);
+        assert_eq!(
+            decode_binary_base64url(5, &polyline[..polyline.len() - 2]).collect::<Vec<_>>(),
+            points[..1]
+        );
+        assert_eq!(decode_binary_base64url(5, "!!!!").next(), None);
+        assert_eq!(decode_binary_base64url(5, "________________").next(), None);
+    }
+}
@@ -0,0 +1,305 @@
+//! Optional self-describing header for the binary format, so the decoder doesn't need to know the scale.
+//!
+//! Layout of the header, followed by the same data as produced by [`encode_binary()`](crate::encode_binary):
+//! - magic bytes `80 00 'P' 'L'`. `80 00` is an over-long varint of zero, which `encode_binary()`
+//!   never produces, so headerless data is never mistaken for data with a header.
+//! - format version, currently `1`.
+//! - flags: `0x01` if the scale is stored as `f64` instead of precision, `0x02` if the point count is stored.
+//! - precision as a single byte, or scale factor as little-endian `f64`.
+//! - number of dimensions per point, currently always `2`.
+//! - point count as a 7-bit varint, if present.
+
+use crate::{BinaryPolylineIter, Scale, encode_binary_scaled, varint64_encode7};
+
+const MAGIC: [u8; 4] = [0x80, 0x00, b'P', b'L'];
+const FORMAT_VERSION: u8 = 1;
+
+const HAS_FACTOR: u8 = 0x01;
+const HAS_COUNT: u8 = 0x02;
+
+/// Metadata stored in the header written by [`encode_binary_with_header()`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BinaryHeader {
+    /// Scale the points were encoded with, stored as a single byte when it is a precision from 0 to 7.
+    pub scale: Scale,
+    /// Number of values per point, `2` for latitude and longitude.
+    pub dimensions: u8,
+    /// Number of points, if it was requested at encode time.
+    pub count: Option<u64>,
+}
+
+/// Error returned by [`decode_binary_auto()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryHeaderError {
+    /// The data doesn't start with the header, use [`decode_binary()`](crate::decode_binary) with a known precision.
+    Missing,
+    /// The data ends in the middle of the header or the header contains invalid values.
+    Malformed,
+    /// The format version is not supported.
+    UnsupportedVersion(u8),
+    /// Points have a number of dimensions this version of the crate can't decode.
+    UnsupportedDimensions(u8),
+}
+
+impl std::fmt::Display for BinaryHeaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinaryHeaderError::Missing => write!(f, "binary polyline has no header"),
+            BinaryHeaderError::Malformed => write!(f, "malformed binary polyline header"),
+            BinaryHeaderError::UnsupportedVersion(version) => {
+                write!(f, "unsupported binary polyline version {version}")
+            }
+            BinaryHeaderError::UnsupportedDimensions(dimensions) => {
+                write!(f, "unsupported number of dimensions {dimensions}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BinaryHeaderError {}
+
+/// Encodes points into the binary format prefixed with a header describing the scale,
+/// so they can be decoded by [`decode_binary_auto()`] without knowing it.
+/// If `with_count` is set, the number of points is stored in the header as well.
+///
+/// ```
+/// use polyline_iter::Scale;
+///
+/// let points = [(55.58513, 12.99958), (55.61461, 13.04627)];
+/// let binary = polyline_iter::encode_binary_with_header(Scale::from_precision(5), true, points);
+///
+/// // The header takes 9 bytes with precision and the point count
+/// assert_eq!(binary[9..], polyline_iter::encode_binary(5, points));
+///
+/// let (header, iter) = polyline_iter::decode_binary_auto(&binary).unwrap();
+/// assert_eq!(header.scale, Scale::from_precision(5));
+/// assert_eq!(header.count, Some(2));
+/// assert_eq!(iter.collect::<Vec<_>>(), points);
+/// ```
+pub fn encode_binary_with_header(
+    scale: Scale,
+    with_count: bool,
+    points: impl IntoIterator<Item = (f64, f64)>,
+) -> Vec<u8> {
+    let body = encode_binary_scaled(scale, points);
+    let precision = (0..=7).find(|&precision| Scale::from_precision(precision) == scale);
+
+    let mut result = Vec::with_capacity(body.len() + 24);
+    result.extend_from_slice(&MAGIC);
+    result.push(FORMAT_VERSION);
+    let mut flags = 0;
+    if precision.is_none() {
+        flags |= HAS_FACTOR;
+    }
+    if with_count {
+        flags |= HAS_COUNT;
+    }
+    result.push(flags);
+    match precision {
+        Some(precision) => result.push(precision),
+        None => result.extend_from_slice(&scale.factor().to_le_bytes()),
+    }
+    result.push(2);
+    if with_count {
+        // Each varint is a point, as both coordinates are interleaved into a single number.
+        let count = body.iter().filter(|&&byte| byte & 0x80 == 0).count();
+        varint64_encode7(count as u64, &mut result);
+    }
+    result.extend_from_slice(&body);
+    result
+}
+
+/// Reads the header written by [`encode_binary_with_header()`] and returns it together with
+/// an iterator over the points that follow it.
+///
+/// Data encoded without a header is rejected with [`BinaryHeaderError::Missing`],
+/// and can still be decoded with [`decode_binary()`](crate::decode_binary).
+///
+/// ```
+/// use polyline_iter::{BinaryHeaderError, Scale};
+///
+/// let points = [(55.5, 13.25)];
+/// let binary = polyline_iter::encode_binary_with_header(Scale::from_factor(3600.0), false, points);
+/// let (header, iter) = polyline_iter::decode_binary_auto(&binary).unwrap();
+/// assert_eq!(header.scale.factor(), 3600.0);
+/// assert_eq!(header.count, None);
+/// assert_eq!(iter.collect::<Vec<_>>(), points);
+///
+/// let headerless = polyline_iter::encode_binary(5, points);
+/// assert_eq!(polyline_iter::decode_binary_auto(&headerless).err(), Some(BinaryHeaderError::Missing));
+/// ```
+pub fn decode_binary_auto(
+    data: &[u8],
+) -> Result<(BinaryHeader, BinaryPolylineIter<'_>), BinaryHeaderError> {
+    let Some(mut data) = data.strip_prefix(&MAGIC) else {
+        return Err(BinaryHeaderError::Missing);
+    };
+    let mut next_byte = || {
+        let (&byte, rest) = data.split_first().ok_or(BinaryHeaderError::Malformed)?;
+        data = rest;
+        Ok(byte)
+    };
+
+    let version = next_byte()?;
+    if version != FORMAT_VERSION {
+        return Err(BinaryHeaderError::UnsupportedVersion(version));
+    }
+    let flags = next_byte()?;
+    if flags & !(HAS_FACTOR | HAS_COUNT) != 0 {
+        return Err(BinaryHeaderError::Malformed);
+    }
+    let scale = if flags & HAS_FACTOR != 0 {
+        let mut bytes = [0; 8];
+        for byte in &mut bytes {
+            *byte = next_byte()?;
+        }
+        let factor = f64::from_le_bytes(bytes);
+        if !factor.is_finite() || factor <= 0.0 {
+            return Err(BinaryHeaderError::Malformed);
+        }
+        Scale::from_factor(factor)
+    } else {
+        let precision = next_byte()?;
+        if precision > 7 {
+            return Err(BinaryHeaderError::Malformed);
+        }
+        Scale::from_precision(precision)
+    };
+    let dimensions = next_byte()?;
+    if dimensions != 2 {
+        return Err(BinaryHeaderError::UnsupportedDimensions(dimensions));
+    }
+
+    let mut iter = BinaryPolylineIter::with_scale(scale, data);
+    let count = if flags & HAS_COUNT != 0 {
+        Some(iter.varint_decode().ok_or(BinaryHeaderError::Malformed)?)
+    } else {
+        None
+    };
+    let header = BinaryHeader {
+        scale,
+        dimensions,
+        count,
+    };
+    Ok((header, iter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, decode_binary, decode_binary_scaled, encode_binary};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn round_trip() {
+        let points: Vec<_> = decode(5, "angrIk~inAgwDybH_|D_{KeoEwtLozFo`Gre@tcA").collect();
+        for scale in [
+            Scale::from_precision(0),
+            Scale::from_precision(5),
+            Scale::from_precision(7),
+            Scale::from_factor(3600.0),
+            Scale::from_factor((1 << 20) as f64),
+        ] {
+            for with_count in [false, true] {
+                let binary = encode_binary_with_header(scale, with_count, points.iter().copied());
+                let (header, iter) = decode_binary_auto(&binary).unwrap();
+                assert_eq!(header.scale, scale);
+                assert_eq!(header.dimensions, 2);
+                assert_eq!(header.count, with_count.then_some(points.len() as u64));
+                let decoded: Vec<_> = iter.collect();
+                if let Some(count) = header.count {
+                    assert_eq!(count, decoded.len() as u64);
+                }
+                assert_eq!(
+                    decoded,
+                    decode_binary_scaled(
+                        scale,
+                        &encode_binary_scaled(scale, points.iter().copied())
+                    )
+                    .collect::<Vec<_>>()
+                );
+            }
+        }
+
+        let binary = encode_binary_with_header(Scale::from_precision(6), true, []);
+        let (header, mut iter) = decode_binary_auto(&binary).unwrap();
+        assert_eq!(header.count, Some(0));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn headerless_compatibility() {
+        let points = [(0.0, 0.0), (55.58513, 12.99958)];
+        let headerless = encode_binary(5, points);
+        assert_eq!(headerless[0], 0x00);
+        assert_eq!(decode_binary(5, &headerless).collect::<Vec<_>>(), points);
+        assert_eq!(
+            decode_binary_auto(&headerless).err(),
+            Some(BinaryHeaderError::Missing)
+        );
+        assert_eq!(
+            decode_binary_auto(&[]).err(),
+            Some(BinaryHeaderError::Missing)
+        );
+
+        // Headerless decoder reads the magic as a zero point, but the following bytes break it
+        let binary = encode_binary_with_header(Scale::from_precision(5), false, points);
+        let misread: Vec<_> = decode_binary(5, &binary).collect();
+        assert_eq!(misread.first(), Some(&(0.0, 0.0)));
+        assert_ne!(misread, points);
+
+        // The count covers every point of the body, including ones with multi-byte varints
+        let far = [(-89.99999, 179.99999), (89.99999, -179.99999), (0.0, 0.0)];
+        let binary = encode_binary_with_header(Scale::from_precision(5), true, far);
+        let (header, iter) = decode_binary_auto(&binary).unwrap();
+        assert_eq!(header.count, Some(iter.count() as u64));
+        assert_eq!(header.count, Some(far.len() as u64));
+    }
+
+    #[test]
+    fn malformed() {
+        let binary = encode_binary_with_header(Scale::from_precision(5), true, [(1.0, 2.0)]);
+        // Truncated in every position of the 9-byte header
+        for len in MAGIC.len()..9 {
+            assert_eq!(
+                decode_binary_auto(&binary[..len]).err(),
+                Some(BinaryHeaderError::Malformed)
+            );
+        }
+
+        let mut bad = binary.clone();
+        bad[4] = 2;
+        assert_eq!(
+            decode_binary_auto(&bad).err(),
+            Some(BinaryHeaderError::UnsupportedVersion(2))
+        );
+
+        let mut bad = binary.clone();
+        bad[5] |= 0x80;
+        assert_eq!(
+            decode_binary_auto(&bad).err(),
+            Some(BinaryHeaderError::Malformed)
+        );
+
+        let mut bad = binary.clone();
+        bad[6] = 8;
+        assert_eq!(
+            decode_binary_auto(&bad).err(),
+            Some(BinaryHeaderError::Malformed)
+        );
+
+        let mut bad = binary.clone();
+        bad[7] = 3;
+        assert_eq!(
+            decode_binary_auto(&bad).err(),
+            Some(BinaryHeaderError::UnsupportedDimensions(3))
+        );
+
+        let mut bad = encode_binary_with_header(Scale::from_factor(3.0), false, []);
+        bad[6..14].copy_from_slice(&f64::NAN.to_le_bytes());
+        assert_eq!(
+            decode_binary_auto(&bad).err(),
+            Some(BinaryHeaderError::Malformed)
+        );
+    }
+}
@@ -0,0 +1,479 @@
+//! Block-packed binary layout in the style of PFOR, which trades some size for decoding speed and skipping.
+//!
+//! Points are split into blocks of [`BLOCK_SIZE`] and zigzag-encoded changes of each block are bit-packed
+//! with the width of the largest one, separately for latitude and longitude. So unpacking is a branchless
+//! loop over fixed-width values that compilers can vectorize, instead of byte-wise varint decoding.
+//!
+//! Layout, all numbers are little-endian:
+//! - the number of points as a 7-bit varint.
+//! - blocks of up to [`BLOCK_SIZE`] points, each with a 10-byte header followed by the packed changes:
+//!   - latitude and longitude of the first point of the block, multiplied by the scale, as `i32`.
+//!     So a block can be decoded without the previous ones, and the large change from `(0, 0)`
+//!     to the first point doesn't widen the first block.
+//!   - bit width of latitude changes and bit width of longitude changes, from 0 to 32, as `u8`.
+//!   - latitude changes of the rest of the points packed from the least significant bit,
+//!     padded to a whole byte, then longitude changes packed the same way.
+
+use crate::{
+    BinaryPolylineIter, Scale, bitwise_merge, bitwise_split, scale_point, varint64_decode7,
+    varint64_encode7, zigzag_decode, zigzag_encode,
+};
+
+/// Number of points in each block except the last one.
+pub const BLOCK_SIZE: usize = 128;
+
+const BLOCK_HEADER_SIZE: usize = 10;
+
+/// Iterator over geographic coordinates decoded from block-packed data, created by [`decode_binary_packed()`].
+///
+/// Unpacks a whole block at once and skips whole blocks without unpacking them in [`Iterator::nth()`].
+///
+/// ```
+/// let points: Vec<_> = (0..1000).map(|i| (55.5 + 0.0001 * i as f64, 13.0 - 0.0002 * i as f64)).collect();
+/// let packed = polyline_iter::encode_binary_packed(5, points.iter().copied());
+///
+/// let mut iter = polyline_iter::decode_binary_packed(5, &packed);
+/// assert_eq!(iter.len(), 1000);
+/// // Skips 7 blocks by reading only their headers
+/// assert_eq!(iter.nth(900), Some(points[900]));
+/// assert_eq!(iter.len(), 99);
+/// ```
+pub struct BinaryPolylineIterPacked<'a> {
+    /// Blocks that are not unpacked yet.
+    data: &'a [u8],
+    scale: f64,
+    /// Number of points left to yield, including the rest of the current block.
+    remaining: u64,
+    /// Latitudes of the current block, multiplied by the scale.
+    lat: [i32; BLOCK_SIZE],
+    /// Longitudes of the current block, multiplied by the scale.
+    lon: [i32; BLOCK_SIZE],
+    /// Index of the next point to yield in the current block.
+    position: usize,
+    /// Number of points in the current block.
+    block_len: usize,
+}
+
+impl<'a> BinaryPolylineIterPacked<'a> {
+    /// Creates a new iterator over points decoded from block-packed data.
+    pub fn new(precision: u8, data: &'a [u8]) -> Self {
+        assert!(precision <= 7, "i32 can hold up to 180 * 10^7");
+        Self::with_scale(Scale::from_precision(precision), data)
+    }
+
+    /// Creates a new iterator over points decoded from block-packed data encoded with an arbitrary [`Scale`].
+    pub fn with_scale(scale: Scale, mut data: &'a [u8]) -> Self {
+        let remaining = varint64_decode7(&mut data).unwrap_or(0);
+        BinaryPolylineIterPacked {
+            data,
+            scale: scale.factor(),
+            remaining,
+            lat: [0; BLOCK_SIZE],
+            lon: [0; BLOCK_SIZE],
+            position: 0,
+            block_len: 0,
+        }
+    }
+
+    /// Number of points left, as stored in the data. O(1) unlike other iterators of this crate.
+    pub fn len(&self) -> usize {
+        self.remaining as usize
+    }
+
+    /// Checks if there are no points left.
+    pub fn is_empty(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Number of points in the next block that is not unpacked yet.
+    fn next_block_len(&self) -> usize {
+        let unpacked = (self.block_len - self.position) as u64;
+        (self.remaining - unpacked).min(BLOCK_SIZE as u64) as usize
+    }
+
+    /// Skips the next block without unpacking it.
+    fn skip_block(&mut self) -> Option<()> {
+        let len = self.next_block_len();
+        let header = BlockHeader::read(self.data)?;
+        self.data = self.data.get(header.size(len)..)?;
+        self.remaining -= len as u64;
+        Some(())
+    }
+
+    /// Unpacks the next block into `lat` and `lon`.
+    fn load_block(&mut self) -> Option<()> {
+        let len = self.next_block_len();
+        let header = BlockHeader::read(self.data)?;
+        let block = self.data.get(..header.size(len))?;
+        header.unpack(block, len, &mut self.lat, &mut self.lon);
+        self.data = &self.data[block.len()..];
+        self.position = 0;
+        self.block_len = len;
+        Some(())
+    }
+
+    /// Stops the iteration on malformed data.
+    fn stop(&mut self) -> Option<(f64, f64)> {
+        self.remaining = 0;
+        self.position = self.block_len;
+        None
+    }
+}
+
+impl Iterator for BinaryPolylineIterPacked<'_> {
+    type Item = (f64, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        if self.position == self.block_len && self.load_block().is_none() {
+            return self.stop();
+        }
+        let (lat, lon) = (self.lat[self.position], self.lon[self.position]);
+        self.position += 1;
+        self.remaining -= 1;
+        Some((lat as f64 / self.scale, lon as f64 / self.scale))
+    }
+
+    fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+        let buffered = self.block_len - self.position;
+        if n < buffered {
+            self.position += n;
+            self.remaining -= n as u64;
+            return self.next();
+        }
+        n -= buffered;
+        self.remaining -= buffered as u64;
+        self.position = self.block_len;
+        while n >= BLOCK_SIZE && self.remaining > BLOCK_SIZE as u64 {
+            if self.skip_block().is_none() {
+                return self.stop();
+            }
+            n -= BLOCK_SIZE;
+        }
+        if n as u64 >= self.remaining {
+            self.remaining = 0;
+            return None;
+        }
+        if self.load_block().is_none() {
+            return self.stop();
+        }
+        self.position = n;
+        self.remaining -= n as u64;
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // The number of points may be wrong only for malformed input.
+        (0, Some(self.len()))
+    }
+}
+
+/// Decodes points from block-packed data created by [`encode_binary_packed()`] or [`binary_to_packed()`].
+pub fn decode_binary_packed(precision: u8, data: &[u8]) -> BinaryPolylineIterPacked<'_> {
+    BinaryPolylineIterPacked::new(precision, data)
+}
+
+/// Encodes a sequence of points into the block-packed binary layout.
+///
+/// The stored changes are exactly the same as in [`encode_binary()`](crate::encode_binary), so decoded points
+/// are the same as well. The output is usually a few percent larger, as each change takes as many bits as the
+/// largest one in its block, e.g. 1423 and 20584 bytes instead of 1408 and 20004 bytes for the medium and long
+/// polylines from the benchmarks.
+///
+/// ```
+/// let points = [(55.58513, 12.99958), (55.61461, 13.04627)];
+/// let packed = polyline_iter::encode_binary_packed(5, points);
+/// assert_eq!(polyline_iter::decode_binary_packed(5, &packed).collect::<Vec<_>>(), points);
+/// assert_eq!(polyline_iter::packed_to_binary(&packed), polyline_iter::encode_binary(5, points));
+/// ```
+pub fn encode_binary_packed(
+    precision: u8,
+    points: impl IntoIterator<Item = (f64, f64)>,
+) -> Vec<u8> {
+    assert!(precision <= 7, "i32 can hold up to 180 * 10^7");
+    let scale = Scale::from_precision(precision).factor();
+    let mut changes = Vec::with_capacity(16);
+    let mut prev = (0, 0);
+    for point in points {
+        // Changes are computed exactly as in `encode_binary()` to get the same points after decoding
+        let point = scale_point(point, scale);
+        let lat_change = point.0.wrapping_sub(prev.0);
+        let lon_change = point.1.wrapping_sub(prev.1);
+        changes.push((zigzag_encode(lat_change), zigzag_encode(lon_change)));
+        prev = point;
+    }
+    pack(&changes)
+}
+
+/// Converts data created by [`encode_binary()`](crate::encode_binary) into the block-packed layout
+/// without decoding coordinates, so it works for any precision or scale and is lossless.
+/// Conversion stops at the first malformed point, just like [`decode_binary()`](crate::decode_binary).
+///
+/// ```
+/// let binary = polyline_iter::encode_binary(6, [(55.585137, 12.999583), (55.644854, 13.112187)]);
+/// let packed = polyline_iter::binary_to_packed(&binary);
+/// assert_eq!(polyline_iter::packed_to_binary(&packed), binary);
+/// ```
+pub fn binary_to_packed(binary: &[u8]) -> Vec<u8> {
+    let mut iter = BinaryPolylineIter::new(0, binary);
+    let mut changes = Vec::with_capacity(binary.len() / 2);
+    while let Some(value) = iter.varint_decode() {
+        changes.push(bitwise_split(value));
+    }
+    pack(&changes)
+}
+
+/// Converts block-packed data back into the format of [`encode_binary()`](crate::encode_binary),
+/// see [`binary_to_packed()`]. Conversion stops at the first malformed block.
+pub fn packed_to_binary(mut packed: &[u8]) -> Vec<u8> {
+    let mut remaining = varint64_decode7(&mut packed).unwrap_or(0);
+    let mut result = Vec::with_capacity(packed.len());
+    let mut lat = [0; BLOCK_SIZE];
+    let mut lon = [0; BLOCK_SIZE];
+    let mut prev = (0i32, 0i32);
+    while remaining > 0 {
+        let len = remaining.min(BLOCK_SIZE as u64) as usize;
+        let Some(header) = BlockHeader::read(packed) else {
+            break;
+        };
+        let Some(block) = packed.get(..header.size(len)) else {
+            break;
+        };
+        header.unpack(block, len, &mut lat, &mut lon);
+        for i in 0..len {
+            // Wrapping is lossless, as `encode_binary()` output is decoded with wrapping as well
+            let lat_change = zigzag_encode(lat[i].wrapping_sub(prev.0));
+            let lon_change = zigzag_encode(lon[i].wrapping_sub(prev.1));
+            varint64_encode7(bitwise_merge(lat_change, lon_change), &mut result);
+            prev = (lat[i], lon[i]);
+        }
+        packed = &packed[block.len()..];
+        remaining -= len as u64;
+    }
+    result
+}
+
+struct BlockHeader {
+    /// Latitude of the first point of the block, multiplied by the scale.
+    lat: i32,
+    /// Longitude of the first point of the block, multiplied by the scale.
+    lon: i32,
+    lat_width: u32,
+    lon_width: u32,
+}
+
+impl BlockHeader {
+    fn read(data: &[u8]) -> Option<Self> {
+        let header: &[u8; BLOCK_HEADER_SIZE] = data.first_chunk()?;
+        let lat_width = header[8] as u32;
+        let lon_width = header[9] as u32;
+        if lat_width > 32 || lon_width > 32 {
+            return None;
+        }
+        Some(BlockHeader {
+            lat: i32::from_le_bytes(header[0..4].try_into().unwrap()),
+            lon: i32::from_le_bytes(header[4..8].try_into().unwrap()),
+            lat_width,
+            lon_width,
+        })
+    }
+
+    /// Size of the block with `len` points in bytes, including the header.
+    fn size(&self, len: usize) -> usize {
+        let changes = len.saturating_sub(1);
+        BLOCK_HEADER_SIZE
+            + packed_size(changes, self.lat_width)
+            + packed_size(changes, self.lon_width)
+    }
+
+    /// Unpacks `len` points of the block, including the header, into absolute values.
+    fn unpack(
+        &self,
+        block: &[u8],
+        len: usize,
+        lat: &mut [i32; BLOCK_SIZE],
+        lon: &mut [i32; BLOCK_SIZE],
+    ) {
+        let changes_len = len.saturating_sub(1);
+        let (lat_data, lon_data) =
+            block[BLOCK_HEADER_SIZE..].split_at(packed_size(changes_len, self.lat_width));
+        let mut changes = [0; BLOCK_SIZE];
+        unpack(lat_data, self.lat_width, &mut changes[..changes_len]);
+        prefix_sum(self.lat, &changes[..changes_len], lat);
+        unpack(lon_data, self.lon_width, &mut changes[..changes_len]);
+        prefix_sum(self.lon, &changes[..changes_len], lon);
+    }
+}
+
+fn packed_size(len: usize, width: u32) -> usize {
+    (len * width as usize).div_ceil(8)
+}
+
+fn pack(changes: &[(u32, u32)]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(changes.len() * 4 + 8);
+    varint64_encode7(changes.len() as u64, &mut result);
+
+    let mut lat = 0i32;
+    let mut lon = 0i32;
+    for block in changes.chunks(BLOCK_SIZE) {
+        // The first point is stored as is, so its change is only needed to get there
+        let (first, rest) = block.split_first().unwrap();
+        lat = lat.wrapping_add(zigzag_decode(first.0));
+        lon = lon.wrapping_add(zigzag_decode(first.1));
+
+        let lat_width = 32
+            - rest
+                .iter()
+                .fold(0, |acc, change| acc | change.0)
+                .leading_zeros();
+        let lon_width = 32
+            - rest
+                .iter()
+                .fold(0, |acc, change| acc | change.1)
+                .leading_zeros();
+        result.extend_from_slice(&lat.to_le_bytes());
+        result.extend_from_slice(&lon.to_le_bytes());
+        result.push(lat_width as u8);
+        result.push(lon_width as u8);
+        pack_values(rest.iter().map(|change| change.0), lat_width, &mut result);
+        pack_values(rest.iter().map(|change| change.1), lon_width, &mut result);
+
+        for &(lat_change, lon_change) in rest {
+            lat = lat.wrapping_add(zigzag_decode(lat_change));
+            lon = lon.wrapping_add(zigzag_decode(lon_change));
+        }
+    }
+    result
+}
+
+/// Writes the lowest `width` bits of each value, starting from the least significant bit.
+fn pack_values(values: impl Iterator<Item = u32>, width: u32, buffer: &mut Vec<u8>) {
+    let mut bits = 0u64;
+    let mut bits_len = 0;
+    for value in values {
+        bits |= (value as u64) << bits_len;
+        bits_len += width;
+        while bits_len >= 8 {
+            buffer.push(bits as u8);
+            bits >>= 8;
+            bits_len -= 8;
+        }
+    }
+    if bits_len > 0 {
+        buffer.push(bits as u8);
+    }
+}
+
+/// Reverse of [`pack_values`]. Each value is read from an unaligned 64-bit word without branches,
+/// so the loop can be vectorized.
+fn unpack(data: &[u8], width: u32, values: &mut [u32]) {
+    // Copied into a zero-padded buffer, so 8 bytes can be read at any value offset
+    let mut padded = [0u8; BLOCK_SIZE * 4 + 8];
+    padded[..data.len()].copy_from_slice(data);
+    let mask = ((1u64 << width) - 1) as u32;
+    let width = width as usize;
+    for (i, value) in values.iter_mut().enumerate() {
+        let bit = i * width;
+        let word = u64::from_le_bytes(*padded[bit / 8..].first_chunk().unwrap());
+        *value = (word >> (bit % 8)) as u32 & mask;
+    }
+}
+
+/// Converts changes into absolute values, starting from the first one.
+fn prefix_sum(mut base: i32, changes: &[u32], values: &mut [i32; BLOCK_SIZE]) {
+    values[0] = base;
+    for (value, &change) in values[1..].iter_mut().zip(changes) {
+        // Wrapping to avoid overflow panics on malformed input
+        base = base.wrapping_add(zigzag_decode(change));
+        *value = base;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::{LONG_POLYLINE6, MEDIUM_POLYLINE6};
+    use crate::{decode, decode_binary, encode_binary};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn same_points_as_binary() {
+        for polyline in [MEDIUM_POLYLINE6, LONG_POLYLINE6] {
+            let points: Vec<_> = decode(6, polyline).collect();
+            let packed = encode_binary_packed(6, points.iter().copied());
+            let iter = decode_binary_packed(6, &packed);
+            assert_eq!(iter.len(), points.len());
+            assert_eq!(iter.collect::<Vec<_>>(), points);
+
+            let binary = encode_binary(6, points.iter().copied());
+            assert_eq!(binary_to_packed(&binary), packed);
+            assert_eq!(packed_to_binary(&packed), binary);
+        }
+
+        // Extreme changes take all 32 bits
+        let points = [
+            (i32::MAX as f64, i32::MIN as f64),
+            (0.0, -1.0),
+            (i32::MIN as f64, 0.0),
+        ];
+        let packed = encode_binary_packed(0, points);
+        assert_eq!(decode_binary_packed(0, &packed).collect::<Vec<_>>(), points);
+        assert_eq!(packed_to_binary(&packed), encode_binary(0, points));
+
+        assert_eq!(encode_binary_packed(5, []), [0]);
+        assert_eq!(decode_binary_packed(5, &[0]).next(), None);
+        assert_eq!(decode_binary_packed(5, &[]).next(), None);
+    }
+
+    #[test]
+    fn skipping() {
+        let points: Vec<_> = decode(6, LONG_POLYLINE6).collect();
+        assert!(points.len() > BLOCK_SIZE * 3);
+        let packed = encode_binary_packed(6, points.iter().copied());
+
+        for n in [0, 1, 127, 128, 129, 255, 256, 300, points.len() - 1] {
+            let mut iter = decode_binary_packed(6, &packed);
+            assert_eq!(iter.nth(n), Some(points[n]), "nth({n})");
+            assert_eq!(iter.len(), points.len() - n - 1);
+            assert_eq!(iter.next(), points.get(n + 1).copied());
+        }
+        let mut iter = decode_binary_packed(6, &packed);
+        assert_eq!(iter.nth(points.len()), None);
+        assert_eq!(iter.next(), None);
+
+        // Skipping from the middle of a block
+        let mut iter = decode_binary_packed(6, &packed);
+        assert_eq!(iter.nth(100), Some(points[100]));
+        assert_eq!(iter.nth(200), Some(points[301]));
+        assert_eq!(
+            iter.skip(5).step_by(128).collect::<Vec<_>>(),
+            points[307..]
+                .iter()
+                .copied()
+                .step_by(128)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn broken_data() {
+        let points: Vec<_> = decode(6, LONG_POLYLINE6).collect();
+        let packed = encode_binary_packed(6, points.iter().copied());
+
+        // Complete blocks are still decoded
+        let truncated = &packed[..packed.len() / 2];
+        let decoded: Vec<_> = decode_binary_packed(6, truncated).collect();
+        assert_eq!(decoded.len() % BLOCK_SIZE, 0);
+        assert_eq!(decoded, points[..decoded.len()]);
+        let binary = packed_to_binary(truncated);
+        assert_eq!(decode_binary(6, &binary).collect::<Vec<_>>(), decoded);
+
+        // Invalid bit width
+        let mut bad = encode_binary_packed(5, [(1.0, 2.0)]);
+        bad[9] = 33;
+        assert_eq!(decode_binary_packed(5, &bad).next(), None);
+        assert!(packed_to_binary(&bad).is_empty());
+    }
+}
@@ -0,0 +1,343 @@
+//! Bing Maps / Azure Maps [Point Compression Algorithm](https://learn.microsoft.com/en-us/bingmaps/rest-services/elevations/point-compression-algorithm).
+//!
+//! Coordinates are stored with precision 5 as zigzag-encoded deltas, but unlike Google's polyline both
+//! deltas of a point are combined into a single number by the Cantor pairing function, which is then
+//! stored as a 5-bit varint with URL-safe characters.
+//!
+//! ```
+//! let points = [
+//!     (35.89431, -110.72522),
+//!     (35.89393, -110.72578),
+//!     (35.89374, -110.72606),
+//!     (35.89337, -110.72662),
+//! ];
+//! assert_eq!(polyline_iter::bing::encode(points), "vx1vilihnM6hR7mEl2Q");
+//! assert_eq!(polyline_iter::bing::decode("vx1vilihnM6hR7mEl2Q").collect::<Vec<_>>(), points);
+//! ```
+
+use crate::base64url::decoding_table;
+use crate::checked::check_point;
+use crate::{EncodeError, EncodeErrorKind, RangePolicy, zigzag_decode, zigzag_encode};
+
+/// The format always uses 5 decimal places.
+const SCALE: f64 = 100_000.0;
+
+/// URL-safe base64 alphabet, but with `_` and `-` swapped compared to RFC 4648.
+const ENCODING_TABLE: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789_-";
+
+/// Reverse of [`ENCODING_TABLE`], with `0xFF` for bytes outside of the alphabet.
+const DECODING_TABLE: [u8; 256] = decoding_table(ENCODING_TABLE);
+
+/// Iterator over geographic coordinates decoded from a Bing Maps compressed point string.
+///
+/// Created by [`decode()`]. Just like [`PolylineIter`](crate::PolylineIter), stops on the first malformed point.
+pub struct BingPolylineIter<'a> {
+    polyline: &'a [u8],
+    /// Last processed latitude, multiplied by the scale.
+    lat: i32,
+    /// Last processed longitude, multiplied by the scale.
+    lon: i32,
+}
+
+impl<'a> BingPolylineIter<'a> {
+    /// Creates a new iterator over points decoded from a compressed point string.
+    #[inline(always)]
+    pub fn new(polyline: &'a str) -> Self {
+        BingPolylineIter {
+            polyline: polyline.as_bytes(),
+            lat: 0,
+            lon: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn varint_decode(&mut self) -> Option<u64> {
+        let mut result = 0;
+        // Pairing of two zigzag-encoded i32 fits into 64 bits, which takes at most 13 chars
+        for i in 0..self.polyline.len().min(13) {
+            let chunk = DECODING_TABLE[self.polyline[i] as usize];
+            if chunk == 0xFF {
+                return None;
+            }
+            result |= ((chunk & 0x1f) as u64) << (i * 5); // no shift overflow as i < 13
+            if chunk & 0x20 == 0 {
+                self.polyline = &self.polyline[i + 1..];
+                return Some(result);
+            }
+        }
+        None
+    }
+
+    /// O(n) operation to count the number of points in the polyline without consuming the iterator.
+    pub fn len(&self) -> usize {
+        self.polyline
+            .iter()
+            .filter(|&&byte| DECODING_TABLE[byte as usize] & 0x20 == 0)
+            .count()
+    }
+
+    /// Checks if the polyline contains no points.
+    pub fn is_empty(&self) -> bool {
+        !self
+            .polyline
+            .iter()
+            .any(|&byte| DECODING_TABLE[byte as usize] & 0x20 == 0)
+    }
+}
+
+impl Iterator for BingPolylineIter<'_> {
+    type Item = (f64, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (lat_change, lon_change) = cantor_unpair(self.varint_decode()?);
+        // Wrapping to avoid overflow panics on malformed input
+        self.lat = self.lat.wrapping_add(zigzag_decode(lat_change));
+        self.lon = self.lon.wrapping_add(zigzag_decode(lon_change));
+        Some((self.lat as f64 / SCALE, self.lon as f64 / SCALE))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each point takes from 1 to 13 chars.
+        let len = self.polyline.len();
+        (len / 13, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+/// Decodes a Bing Maps compressed point string into an iterator over geographic coordinates.
+///
+/// ```
+/// let mut iter = polyline_iter::bing::decode("vx1vilihnM6hR");
+/// assert_eq!(iter.len(), 2);
+/// assert_eq!(iter.next(), Some((35.89431, -110.72522)));
+/// assert_eq!(iter.next(), Some((35.89393, -110.72578)));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[inline(always)]
+pub fn decode(polyline: &str) -> BingPolylineIter<'_> {
+    BingPolylineIter::new(polyline)
+}
+
+/// Encodes a sequence of points (latitude, longitude pairs) with the Point Compression Algorithm.
+///
+/// Note that the reference implementation emits nothing for a point that doesn't move, which makes such points
+/// disappear on decoding. This implementation emits `A` instead, which any conformant decoder reads as zero change.
+///
+/// Out of range coordinates are normalized as with [`RangePolicy::Normalize`], and NaN or infinite ones
+/// are encoded as zero, so any input produces a decodable string. Use [`try_encode()`] to reject such points.
+///
+/// ```
+/// let points = [(35.89431, -110.72522), (35.89431, -110.72522)];
+/// assert_eq!(polyline_iter::bing::encode(points), "vx1vilihnMA");
+/// assert_eq!(polyline_iter::bing::encode([(95.0, 190.0)]), polyline_iter::bing::encode([(90.0, -170.0)]));
+/// ```
+pub fn encode(points: impl IntoIterator<Item = (f64, f64)>) -> String {
+    let finite = |value: f64| if value.is_finite() { value } else { 0.0 };
+    let points = points
+        .into_iter()
+        .map(|(lat, lon)| (finite(lat), finite(lon)));
+    try_encode_with(RangePolicy::Normalize, points)
+        .expect("normalized coordinates always fit into the format")
+}
+
+/// Like [`encode()`], but returns an error instead of encoding NaN, infinite or out of range coordinates.
+///
+/// ```
+/// use polyline_iter::{EncodeError, EncodeErrorKind};
+///
+/// assert_eq!(polyline_iter::bing::try_encode([(35.89431, -110.72522)]), Ok("vx1vilihnM".to_string()));
+/// assert_eq!(
+///     polyline_iter::bing::try_encode([(0.0, 0.0), (20000.0, -20000.0)]),
+///     Err(EncodeError { index: 1, kind: EncodeErrorKind::OutOfRange })
+/// );
+/// ```
+pub fn try_encode(points: impl IntoIterator<Item = (f64, f64)>) -> Result<String, EncodeError> {
+    try_encode_with(RangePolicy::Reject, points)
+}
+
+/// Like [`try_encode()`], but with the explicit policy for out of range coordinates.
+/// With [`RangePolicy::Allow`], points whose scaled coordinates or paired deltas don't fit into the format
+/// fail with [`EncodeErrorKind::DeltaOverflow`].
+pub fn try_encode_with(
+    policy: RangePolicy,
+    points: impl IntoIterator<Item = (f64, f64)>,
+) -> Result<String, EncodeError> {
+    let mut result = String::with_capacity(16);
+
+    // Unlike Google's polyline, deltas are computed on already scaled values, as in the reference implementation.
+    let mut prev = (0, 0);
+    for (index, point) in points.into_iter().enumerate() {
+        let (lat, lon) = check_point(point, policy).map_err(|kind| EncodeError { index, kind })?;
+        let overflow = EncodeError {
+            index,
+            kind: EncodeErrorKind::DeltaOverflow,
+        };
+        let lat = scaled(lat).ok_or(overflow)?;
+        let lon = scaled(lon).ok_or(overflow)?;
+        let lat_change = zigzag_encode(lat.wrapping_sub(prev.0));
+        let lon_change = zigzag_encode(lon.wrapping_sub(prev.1));
+
+        let mut value = cantor_pair(lat_change, lon_change).ok_or(overflow)?;
+        while value >= 0x20 {
+            result.push(ENCODING_TABLE[((value & 0x1F) | 0x20) as usize] as char);
+            value >>= 5;
+        }
+        result.push(ENCODING_TABLE[value as usize] as char);
+
+        prev = (lat, lon);
+    }
+    Ok(result)
+}
+
+/// Returns the coordinate multiplied by the scale and rounded to `i32`, or `None` if it doesn't fit.
+fn scaled(value: f64) -> Option<i32> {
+    let value = (value * SCALE).round();
+    (value >= i32::MIN as f64 && value <= i32::MAX as f64).then_some(value as i32)
+}
+
+/// Combines two numbers into a single one as `(y + x) * (y + x + 1) / 2 + y`.
+/// Returns `None` if the result doesn't fit into 64 bits, which happens only if `y + x` is above
+/// about `6 * 10^9` and never for valid coordinates.
+fn cantor_pair(y: u32, x: u32) -> Option<u64> {
+    let sum = y as u128 + x as u128;
+    u64::try_from(sum * (sum + 1) / 2 + y as u128).ok()
+}
+
+/// Reverse operation to [`cantor_pair`].
+fn cantor_unpair(value: u64) -> (u32, u32) {
+    // The diagonal is the largest `d` such as `d * (d + 1) / 2 <= value`.
+    // Float estimation can be off by one for big values, so it is corrected with integer math.
+    // Math is done in u128 to not overflow on malformed input.
+    let value = value as u128;
+    let mut diagonal = (((8.0 * value as f64 + 1.0).sqrt() - 1.0) / 2.0) as u128;
+    while diagonal * (diagonal + 1) / 2 > value {
+        diagonal -= 1;
+    }
+    while (diagonal + 1) * (diagonal + 2) / 2 <= value {
+        diagonal += 1;
+    }
+    let y = value - diagonal * (diagonal + 1) / 2;
+    let x = diagonal - y;
+    (y as u32, x as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Example from Microsoft's documentation
+    #[test]
+    fn reference_example() {
+        let points = [
+            (35.894309002906084, -110.72522000409663),
+            (35.89393097907305, -110.72577999904752),
+            (35.89374498464167, -110.72606003843248),
+            (35.893366960808635, -110.72661500424147),
+        ];
+        assert_eq!(encode(points), "vx1vilihnM6hR7mEl2Q");
+        assert_eq!(
+            decode("vx1vilihnM6hR7mEl2Q").collect::<Vec<_>>(),
+            [
+                (35.89431, -110.72522),
+                (35.89393, -110.72578),
+                (35.89374, -110.72606),
+                (35.89337, -110.72662),
+            ]
+        );
+    }
+
+    #[test]
+    fn cantor() {
+        assert_eq!(cantor_pair(0, 0), Some(0));
+        assert_eq!(cantor_pair(0, 1), Some(1));
+        assert_eq!(cantor_pair(1, 0), Some(2));
+        assert_eq!(cantor_pair(0, 2), Some(3));
+        assert_eq!(cantor_pair(1, 1), Some(4));
+        assert_eq!(cantor_pair(2, 0), Some(5));
+        for (y, x) in [
+            (0, 0),
+            (1, 2),
+            (12345, 67890),
+            (36_000_000, 72_000_000),
+            (u32::MAX / 2, u32::MAX / 2),
+            (u32::MAX, 0),
+            (0, u32::MAX),
+        ] {
+            assert_eq!(cantor_unpair(cantor_pair(y, x).unwrap()), (y, x));
+        }
+        assert_eq!(cantor_pair(u32::MAX, 2_000_000_000), None);
+        assert_eq!(cantor_pair(u32::MAX, u32::MAX), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let points = [
+            (0.0, 0.0),
+            (90.0, 180.0),
+            (-90.0, -180.0),
+            (55.58513, 12.99958),
+            (55.58513, 12.99958),
+            (-54.90653, -65.99208),
+        ];
+        let polyline = encode(points);
+        let iter = decode(&polyline);
+        assert_eq!(iter.len(), points.len());
+        assert!(!iter.is_empty());
+        assert_eq!(iter.collect::<Vec<_>>(), points);
+        assert_eq!(encode([]), "");
+        assert!(decode("").is_empty());
+    }
+
+    #[test]
+    fn invalid_points() {
+        let err = |index, kind| Err(EncodeError { index, kind });
+        assert_eq!(
+            try_encode([(0.0, 0.0), (20000.0, -20000.0)]),
+            err(1, EncodeErrorKind::OutOfRange)
+        );
+        assert_eq!(
+            try_encode([(f64::INFINITY, 0.0)]),
+            err(0, EncodeErrorKind::Infinite)
+        );
+        assert_eq!(try_encode([(0.0, f64::NAN)]), err(0, EncodeErrorKind::NaN));
+        // The scaled coordinate fits into i32, but the zigzag-encoded deltas sum past 2^32
+        assert_eq!(
+            try_encode_with(RangePolicy::Allow, [(20000.0, -20000.0)]),
+            err(0, EncodeErrorKind::DeltaOverflow)
+        );
+        assert_eq!(
+            try_encode_with(RangePolicy::Allow, [(30000.0, 0.0)]),
+            err(0, EncodeErrorKind::DeltaOverflow)
+        );
+        let points = [(1000.0, -1000.0), (-1000.0, 1000.0)];
+        let polyline = try_encode_with(RangePolicy::Allow, points).unwrap();
+        assert_eq!(decode(&polyline).collect::<Vec<_>>(), points);
+
+        // Plain encoding never panics and stays decodable
+        assert_eq!(encode([(20000.0, -20000.0)]), encode([(90.0, 160.0)]));
+        assert_eq!(
+            encode([(f64::INFINITY, f64::NEG_INFINITY), (f64::NAN, 1.0)]),
+            encode([(0.0, 0.0), (0.0, 1.0)])
+        );
+    }
+
+    #[test]
+    fn broken_string() {
+        // Last point is truncated
+        assert_eq!(
+            decode("vx1vilihnM6h").collect::<Vec<_>>(),
+            [(35.89431, -110.72522)]
+        );
+        // `.` is not in the alphabet
+        assert_eq!(
+            decode("vx1vilihnM.hR").collect::<Vec<_>>(),
+            [(35.89431, -110.72522)]
+        );
+        assert_eq!(decode("________________").next(), None);
+    }
+}
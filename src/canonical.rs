@@ -0,0 +1,187 @@
+//! Detection and removal of non-canonical encodings.
+//!
+//! Decoders accept varints with redundant zero chunks, e.g. `"_?"` for zero instead of `"?"`,
+//! so the same points may have several different encodings. Canonical encoding is the one produced
+//! by the encoders, which is unique for every sequence of points and can be compared byte by byte.
+
+use crate::{BinaryPolylineIter, PolylineIter, varint32_encode5, varint64_encode7};
+
+/// Checks whether the polyline is exactly what [`encode()`](crate::encode) produces for its points,
+/// i.e. it has no redundant zero chunks, no characters outside of the polyline alphabet
+/// and no malformed tail.
+///
+/// ```
+/// assert!(polyline_iter::is_canonical("angrIk~inAgwDybH"));
+/// // Latitude of the first point is padded with a zero chunk
+/// assert!(!polyline_iter::is_canonical("angri?k~inA"));
+/// assert!(!polyline_iter::is_canonical("angrIk~in"));
+/// ```
+pub fn is_canonical(polyline: &str) -> bool {
+    let mut iter = PolylineIter::new(0, polyline);
+    // Two numbers take at most 14 chars, so there are no allocations in the loop
+    let mut buffer = String::with_capacity(14);
+    while !iter.polyline.is_empty() {
+        let rest = iter.polyline;
+        let (Some(lat_change), Some(lon_change)) = (iter.varint_decode(), iter.varint_decode())
+        else {
+            return false;
+        };
+        buffer.clear();
+        varint32_encode5(lat_change, &mut buffer);
+        varint32_encode5(lon_change, &mut buffer);
+        if rest[..rest.len() - iter.polyline.len()] != *buffer.as_bytes() {
+            return false;
+        }
+    }
+    true
+}
+
+/// Re-encodes the polyline with the minimal encoding of every number, without converting points into floats.
+/// The result decodes into the same points as the input. Conversion stops at the first malformed point.
+///
+/// ```
+/// assert_eq!(polyline_iter::canonicalize("_??"), "??");
+/// assert_eq!(polyline_iter::canonicalize("angrIk~inAgwDybH"), "angrIk~inAgwDybH");
+/// ```
+pub fn canonicalize(polyline: &str) -> String {
+    let mut iter = PolylineIter::new(0, polyline);
+    let mut result = String::with_capacity(polyline.len());
+    while let (Some(lat_change), Some(lon_change)) = (iter.varint_decode(), iter.varint_decode()) {
+        varint32_encode5(lat_change, &mut result);
+        varint32_encode5(lon_change, &mut result);
+    }
+    result
+}
+
+/// Binary version of [`is_canonical()`], for data created by [`encode_binary()`](crate::encode_binary).
+///
+/// ```
+/// let binary = polyline_iter::encode_binary(5, [(55.58513, 12.99958)]);
+/// assert!(polyline_iter::is_canonical_binary(&binary));
+/// // Zero point padded with a zero chunk
+/// assert!(!polyline_iter::is_canonical_binary(&[0x80, 0x00]));
+/// ```
+pub fn is_canonical_binary(binary: &[u8]) -> bool {
+    let mut iter = BinaryPolylineIter::new(0, binary);
+    let mut buffer = Vec::with_capacity(10);
+    while !iter.polyline.is_empty() {
+        let rest = iter.polyline;
+        let Some(value) = iter.varint_decode() else {
+            return false;
+        };
+        buffer.clear();
+        varint64_encode7(value, &mut buffer);
+        if rest[..rest.len() - iter.polyline.len()] != *buffer {
+            return false;
+        }
+    }
+    true
+}
+
+/// Binary version of [`canonicalize()`].
+///
+/// ```
+/// assert_eq!(polyline_iter::canonicalize_binary(&[0x80, 0x00, 0x81, 0x80, 0x00]), [0x00, 0x01]);
+/// ```
+pub fn canonicalize_binary(binary: &[u8]) -> Vec<u8> {
+    let mut iter = BinaryPolylineIter::new(0, binary);
+    let mut result = Vec::with_capacity(binary.len());
+    while let Some(value) = iter.varint_decode() {
+        varint64_encode7(value, &mut result);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::MEDIUM_POLYLINE6;
+    use crate::{decode, decode_binary, encode, encode_binary};
+    use pretty_assertions::assert_eq;
+
+    /// Pads every varint with redundant zero chunks up to the maximal length.
+    fn pad(polyline: &str) -> String {
+        let mut result = String::new();
+        let mut chunks = 0;
+        for byte in polyline.bytes() {
+            chunks += 1;
+            if (byte - 63) & 0x20 != 0 {
+                result.push(byte as char);
+                continue;
+            }
+            if chunks < 7 {
+                // Set the continuation bit and add zero chunks, the last one without it
+                result.push((byte + 0x20) as char);
+                result.extend(std::iter::repeat_n('_', 6 - chunks));
+                result.push('?');
+            } else {
+                result.push(byte as char);
+            }
+            chunks = 0;
+        }
+        result
+    }
+
+    #[test]
+    fn text() {
+        assert!(is_canonical(""));
+        assert!(is_canonical(MEDIUM_POLYLINE6));
+        assert_eq!(canonicalize(MEDIUM_POLYLINE6), MEDIUM_POLYLINE6);
+
+        let padded = pad(MEDIUM_POLYLINE6);
+        assert!(padded.len() > MEDIUM_POLYLINE6.len());
+        assert_eq!(
+            decode(6, &padded).collect::<Vec<_>>(),
+            decode(6, MEDIUM_POLYLINE6).collect::<Vec<_>>()
+        );
+        assert!(!is_canonical(&padded));
+        assert_eq!(canonicalize(&padded), MEDIUM_POLYLINE6);
+
+        // Only the very last number is padded
+        let mut padded = MEDIUM_POLYLINE6.to_string();
+        let last = padded.pop().unwrap();
+        padded.push((last as u8 + 0x20) as char);
+        padded.push('?');
+        assert!(!is_canonical(&padded));
+        assert_eq!(canonicalize(&padded), MEDIUM_POLYLINE6);
+
+        // Malformed input
+        assert!(!is_canonical("angrIk~inAgwD"));
+        assert_eq!(canonicalize("angrIk~inAgwD"), "angrIk~inA");
+        assert!(!is_canonical("angrIk~inA!!"));
+        assert_eq!(canonicalize("????"), "????");
+        // 7th chunk holds only 2 bits of u32
+        assert!(!is_canonical("~~~~~~C?"));
+        assert_eq!(canonicalize("~~~~~~C?"), "~~~~~^?");
+    }
+
+    #[test]
+    fn binary() {
+        let points: Vec<_> = decode(6, MEDIUM_POLYLINE6).collect();
+        let binary = encode_binary(6, points.iter().copied());
+        assert!(is_canonical_binary(b""));
+        assert!(is_canonical_binary(&binary));
+        assert_eq!(canonicalize_binary(&binary), binary);
+
+        // Pad every point to the maximal 10 bytes
+        let mut padded = Vec::new();
+        for point in binary.split_inclusive(|&byte| byte & 0x80 == 0) {
+            padded.extend_from_slice(&point[..point.len() - 1]);
+            padded.push(point[point.len() - 1] | 0x80);
+            padded.resize(padded.len() + 9 - point.len(), 0x80);
+            padded.push(0x00);
+        }
+        assert_eq!(padded.len(), points.len() * 10);
+        assert_eq!(decode_binary(6, &padded).collect::<Vec<_>>(), points);
+        assert!(!is_canonical_binary(&padded));
+        assert_eq!(canonicalize_binary(&padded), binary);
+
+        assert!(!is_canonical_binary(&binary[..binary.len() - 1]));
+        assert!(!is_canonical_binary(&[0xFF; 10]));
+        assert_eq!(canonicalize_binary(&[0x00, 0xFF, 0xFF]), [0x00]);
+        assert_eq!(
+            encode(6, decode_binary(6, &canonicalize_binary(&padded))),
+            MEDIUM_POLYLINE6
+        );
+    }
+}
@@ -0,0 +1,641 @@
+//! Non-panicking encoders and decoders that validate their input instead of silently producing garbage.
+
+use crate::{BinaryPolylineIter, PolylineIter, bitwise_merge, bitwise_split};
+use crate::{varint32_encode5, varint64_encode7, zigzag_decode, zigzag_encode};
+
+/// Error returned by [`try_encode()`], [`try_encode_binary()`] and [`bing::try_encode()`](crate::bing::try_encode)
+/// for points that can't be encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodeError {
+    /// Index of the offending point in the input sequence.
+    pub index: usize,
+    /// What exactly is wrong with the point.
+    pub kind: EncodeErrorKind,
+}
+
+/// The reason why a point can't be encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeErrorKind {
+    /// Latitude or longitude is NaN.
+    NaN,
+    /// Latitude or longitude is infinite.
+    Infinite,
+    /// Latitude is outside of ±90 or longitude is outside of ±180.
+    OutOfRange,
+    /// The coordinate or the change from the previous point, multiplied by the scale, doesn't fit into `i32`.
+    DeltaOverflow,
+}
+
+impl std::fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self.kind {
+            EncodeErrorKind::NaN => "coordinate is NaN",
+            EncodeErrorKind::Infinite => "coordinate is infinite",
+            EncodeErrorKind::OutOfRange => {
+                "coordinate is outside of ±90 latitude or ±180 longitude"
+            }
+            EncodeErrorKind::DeltaOverflow => {
+                "coordinate or change from the previous point is too large for i32"
+            }
+        };
+        write!(f, "can't encode point #{}: {reason}", self.index)
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/// Defines how [`try_encode_with()`] and [`try_encode_binary_with()`] treat coordinates
+/// outside of ±90 latitude and ±180 longitude.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RangePolicy {
+    /// Fail with [`EncodeErrorKind::OutOfRange`].
+    #[default]
+    Reject,
+    /// Clamp latitude to ±90 and wrap longitude into ±180.
+    Normalize,
+    /// Don't check the range at all, e.g. for planar coordinates.
+    /// Deltas and coordinates accumulated from them are still checked for overflow,
+    /// so the result is always decodable.
+    Allow,
+}
+
+impl RangePolicy {
+    fn apply(self, (lat, lon): (f64, f64)) -> Result<(f64, f64), EncodeErrorKind> {
+        match self {
+            RangePolicy::Reject if lat.abs() > 90.0 || lon.abs() > 180.0 => {
+                Err(EncodeErrorKind::OutOfRange)
+            }
+            RangePolicy::Normalize if lon.abs() > 180.0 => Ok((
+                lat.clamp(-90.0, 90.0),
+                (lon + 180.0).rem_euclid(360.0) - 180.0,
+            )),
+            RangePolicy::Normalize => Ok((lat.clamp(-90.0, 90.0), lon)),
+            _ => Ok((lat, lon)),
+        }
+    }
+}
+
+/// Like [`encode()`](crate::encode), but returns an error instead of encoding NaN, infinite or
+/// out of range coordinates.
+///
+/// ```
+/// use polyline_iter::{EncodeError, EncodeErrorKind};
+///
+/// assert_eq!(
+///     polyline_iter::try_encode(5, [(55.58513, 12.99958), (55.61461, 13.04627)]),
+///     Ok("angrIk~inAgwDybH".to_string())
+/// );
+/// assert_eq!(
+///     polyline_iter::try_encode(5, [(55.58513, 12.99958), (f64::NAN, 13.04627)]),
+///     Err(EncodeError { index: 1, kind: EncodeErrorKind::NaN })
+/// );
+/// assert_eq!(
+///     polyline_iter::try_encode(5, [(91.0, 0.0)]),
+///     Err(EncodeError { index: 0, kind: EncodeErrorKind::OutOfRange })
+/// );
+/// ```
+pub fn try_encode(
+    precision: u8,
+    points: impl IntoIterator<Item = (f64, f64)>,
+) -> Result<String, EncodeError> {
+    try_encode_with(precision, RangePolicy::Reject, points)
+}
+
+/// Like [`try_encode()`], but with the explicit policy for out of range coordinates.
+///
+/// ```
+/// use polyline_iter::{RangePolicy, try_encode_with};
+///
+/// // Latitude is clamped to 90 and longitude is wrapped to -170
+/// assert_eq!(
+///     try_encode_with(5, RangePolicy::Normalize, [(95.0, 190.0)]),
+///     try_encode_with(5, RangePolicy::Reject, [(90.0, -170.0)])
+/// );
+/// ```
+pub fn try_encode_with(
+    precision: u8,
+    policy: RangePolicy,
+    points: impl IntoIterator<Item = (f64, f64)>,
+) -> Result<String, EncodeError> {
+    let mut result = String::with_capacity(16);
+    try_encode_deltas(precision, policy, points, |lat_change, lon_change| {
+        varint32_encode5(zigzag_encode(lat_change), &mut result);
+        varint32_encode5(zigzag_encode(lon_change), &mut result);
+    })?;
+    Ok(result)
+}
+
+/// Like [`encode_binary()`](crate::encode_binary), but returns an error instead of encoding NaN,
+/// infinite or out of range coordinates.
+///
+/// ```
+/// use polyline_iter::{EncodeError, EncodeErrorKind};
+///
+/// let points = [(55.58513, 12.99958), (55.61461, 13.04627)];
+/// assert_eq!(polyline_iter::try_encode_binary(5, points), Ok(polyline_iter::encode_binary(5, points)));
+/// assert_eq!(
+///     polyline_iter::try_encode_binary(5, [(0.0, f64::INFINITY)]),
+///     Err(EncodeError { index: 0, kind: EncodeErrorKind::Infinite })
+/// );
+/// ```
+pub fn try_encode_binary(
+    precision: u8,
+    points: impl IntoIterator<Item = (f64, f64)>,
+) -> Result<Vec<u8>, EncodeError> {
+    try_encode_binary_with(precision, RangePolicy::Reject, points)
+}
+
+/// Like [`try_encode_binary()`], but with the explicit policy for out of range coordinates.
+pub fn try_encode_binary_with(
+    precision: u8,
+    policy: RangePolicy,
+    points: impl IntoIterator<Item = (f64, f64)>,
+) -> Result<Vec<u8>, EncodeError> {
+    let mut result = Vec::with_capacity(16);
+    try_encode_deltas(precision, policy, points, |lat_change, lon_change| {
+        let interleaved = bitwise_merge(zigzag_encode(lat_change), zigzag_encode(lon_change));
+        varint64_encode7(interleaved, &mut result);
+    })?;
+    Ok(result)
+}
+
+/// Validates points and feeds their scaled deltas into `emit`.
+fn try_encode_deltas(
+    precision: u8,
+    policy: RangePolicy,
+    points: impl IntoIterator<Item = (f64, f64)>,
+    mut emit: impl FnMut(i32, i32),
+) -> Result<(), EncodeError> {
+    assert!(precision <= 7, "i32 can hold up to 180 * 10^7");
+    let scale = 10.0_f64.powi(precision as i32);
+
+    let mut prev = (0, 0);
+    for (index, point) in points.into_iter().enumerate() {
+        let point = check_point(point, policy).map_err(|kind| EncodeError { index, kind })?;
+        // Coordinates as the decoder accumulates them and the changes between them must all fit into `i32`
+        let scaled = match (scaled_value(point.0, scale), scaled_value(point.1, scale)) {
+            (Some(lat), Some(lon)) => (lat, lon),
+            _ => {
+                return Err(EncodeError {
+                    index,
+                    kind: EncodeErrorKind::DeltaOverflow,
+                });
+            }
+        };
+        let (Some(lat_change), Some(lon_change)) =
+            (scaled.0.checked_sub(prev.0), scaled.1.checked_sub(prev.1))
+        else {
+            return Err(EncodeError {
+                index,
+                kind: EncodeErrorKind::DeltaOverflow,
+            });
+        };
+        emit(lat_change, lon_change);
+        prev = scaled;
+    }
+    Ok(())
+}
+
+pub(crate) fn check_point(
+    point: (f64, f64),
+    policy: RangePolicy,
+) -> Result<(f64, f64), EncodeErrorKind> {
+    if point.0.is_nan() || point.1.is_nan() {
+        return Err(EncodeErrorKind::NaN);
+    }
+    if point.0.is_infinite() || point.1.is_infinite() {
+        return Err(EncodeErrorKind::Infinite);
+    }
+    policy.apply(point)
+}
+
+/// Returns `value * scale` rounded to `i32`, or `None` if it doesn't fit.
+fn scaled_value(value: f64, scale: f64) -> Option<i32> {
+    let scaled = (value * scale).round();
+    (scaled >= i32::MIN as f64 && scaled <= i32::MAX as f64).then_some(scaled as i32)
+}
+
+/// Error returned by [`try_decode()`] and [`try_decode_binary()`] for malformed input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeError {
+    /// Byte offset of the number that can't be decoded.
+    pub offset: usize,
+    /// What exactly is wrong with the number.
+    pub kind: DecodeErrorKind,
+}
+
+/// The reason why a polyline can't be decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    /// Character outside of the polyline alphabet, from `'?'` to `'~'`.
+    InvalidChar,
+    /// The input ends in the middle of a number or a point.
+    Truncated,
+    /// The number or the coordinate accumulated from the changes doesn't fit into `i32`.
+    Overflow,
+    /// The number has redundant zero chunks, see [`is_canonical()`](crate::is_canonical).
+    NonCanonical,
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self.kind {
+            DecodeErrorKind::InvalidChar => "invalid character",
+            DecodeErrorKind::Truncated => "unexpected end of input",
+            DecodeErrorKind::Overflow => "value is too large for i32",
+            DecodeErrorKind::NonCanonical => "number is not encoded canonically",
+        };
+        write!(f, "can't decode number at offset {}: {reason}", self.offset)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Defines how [`try_decode_with()`] and [`try_decode_binary_with()`] treat numbers
+/// encoded with redundant zero chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CanonicalPolicy {
+    /// Decode them as [`decode()`](crate::decode) does.
+    #[default]
+    Accept,
+    /// Fail with [`DecodeErrorKind::NonCanonical`], so every accepted input has the only possible encoding.
+    Reject,
+}
+
+/// Like [`decode()`](crate::decode), but validates the whole polyline before decoding and returns
+/// an error instead of stopping at the first malformed point or producing garbage coordinates.
+///
+/// ```
+/// use polyline_iter::{DecodeError, DecodeErrorKind};
+///
+/// let iter = polyline_iter::try_decode(5, "angrIk~inAgwDybH").unwrap();
+/// assert_eq!(iter.collect::<Vec<_>>(), [(55.58513, 12.99958), (55.61461, 13.04627)]);
+/// assert_eq!(
+///     polyline_iter::try_decode(5, "angrIk~inAgwD").err(),
+///     Some(DecodeError { offset: 13, kind: DecodeErrorKind::Truncated })
+/// );
+/// assert_eq!(
+///     polyline_iter::try_decode(5, "angrIk~in A").err(),
+///     Some(DecodeError { offset: 5, kind: DecodeErrorKind::InvalidChar })
+/// );
+/// ```
+pub fn try_decode(precision: u8, polyline: &str) -> Result<PolylineIter<'_>, DecodeError> {
+    try_decode_with(precision, CanonicalPolicy::Accept, polyline)
+}
+
+/// Like [`try_decode()`], but with the explicit policy for non-canonical numbers.
+///
+/// ```
+/// use polyline_iter::{CanonicalPolicy, DecodeError, DecodeErrorKind, try_decode_with};
+///
+/// // Latitude of the first point is padded with a zero chunk
+/// assert!(try_decode_with(5, CanonicalPolicy::Accept, "angri?k~inA").is_ok());
+/// assert_eq!(
+///     try_decode_with(5, CanonicalPolicy::Reject, "angri?k~inA").err(),
+///     Some(DecodeError { offset: 0, kind: DecodeErrorKind::NonCanonical })
+/// );
+/// ```
+pub fn try_decode_with(
+    precision: u8,
+    policy: CanonicalPolicy,
+    polyline: &str,
+) -> Result<PolylineIter<'_>, DecodeError> {
+    let iter = PolylineIter::new(precision, polyline);
+    let bytes = polyline.as_bytes();
+    let mut coords = [0i32; 2];
+    let mut offset = 0;
+    let mut numbers = 0;
+    while offset < bytes.len() {
+        let error = |kind| DecodeError { offset, kind };
+        let (value, len, canonical) = checked_varint32_decode5(&bytes[offset..]).map_err(error)?;
+        if !canonical && policy == CanonicalPolicy::Reject {
+            return Err(error(DecodeErrorKind::NonCanonical));
+        }
+        let coord = &mut coords[numbers % 2];
+        *coord = coord
+            .checked_add(zigzag_decode(value))
+            .ok_or(error(DecodeErrorKind::Overflow))?;
+        offset += len;
+        numbers += 1;
+    }
+    if numbers % 2 != 0 {
+        return Err(DecodeError {
+            offset,
+            kind: DecodeErrorKind::Truncated,
+        });
+    }
+    Ok(iter)
+}
+
+/// Like [`decode_binary()`](crate::decode_binary), but validates the whole data before decoding and returns
+/// an error instead of stopping at the first malformed point or producing garbage coordinates.
+///
+/// ```
+/// use polyline_iter::{DecodeError, DecodeErrorKind};
+///
+/// let binary = polyline_iter::encode_binary(5, [(55.58513, 12.99958), (55.61461, 13.04627)]);
+/// assert_eq!(
+///     polyline_iter::try_decode_binary(5, &binary).unwrap().collect::<Vec<_>>(),
+///     [(55.58513, 12.99958), (55.61461, 13.04627)]
+/// );
+/// assert_eq!(
+///     polyline_iter::try_decode_binary(5, &binary[..binary.len() - 1]).err(),
+///     Some(DecodeError { offset: 7, kind: DecodeErrorKind::Truncated })
+/// );
+/// ```
+pub fn try_decode_binary(
+    precision: u8,
+    polyline: &[u8],
+) -> Result<BinaryPolylineIter<'_>, DecodeError> {
+    try_decode_binary_with(precision, CanonicalPolicy::Accept, polyline)
+}
+
+/// Like [`try_decode_binary()`], but with the explicit policy for non-canonical numbers.
+pub fn try_decode_binary_with(
+    precision: u8,
+    policy: CanonicalPolicy,
+    polyline: &[u8],
+) -> Result<BinaryPolylineIter<'_>, DecodeError> {
+    let iter = BinaryPolylineIter::new(precision, polyline);
+    let (mut lat, mut lon) = (0i32, 0i32);
+    let mut offset = 0;
+    while offset < polyline.len() {
+        let error = |kind| DecodeError { offset, kind };
+        let (value, len, canonical) =
+            checked_varint64_decode7(&polyline[offset..]).map_err(error)?;
+        if !canonical && policy == CanonicalPolicy::Reject {
+            return Err(error(DecodeErrorKind::NonCanonical));
+        }
+        let (lat_change, lon_change) = bitwise_split(value);
+        let (Some(next_lat), Some(next_lon)) = (
+            lat.checked_add(zigzag_decode(lat_change)),
+            lon.checked_add(zigzag_decode(lon_change)),
+        ) else {
+            return Err(error(DecodeErrorKind::Overflow));
+        };
+        (lat, lon) = (next_lat, next_lon);
+        offset += len;
+    }
+    Ok(iter)
+}
+
+/// Reads a number written by `varint32_encode5()`, returning the value, the number of chars it takes
+/// and whether it is encoded canonically, i.e. without redundant zero chunks.
+fn checked_varint32_decode5(polyline: &[u8]) -> Result<(u32, usize, bool), DecodeErrorKind> {
+    let mut result = 0;
+    for (i, &byte) in polyline.iter().enumerate() {
+        if !(63..=126).contains(&byte) {
+            return Err(DecodeErrorKind::InvalidChar);
+        }
+        let chunk = (byte - 63) as u32;
+        // 32 bits are stored in at most 7 chunks by 5 bits each, so the 7th chunk can hold only 2 bits.
+        if i == 6 && chunk > 0b11 {
+            return Err(DecodeErrorKind::Overflow);
+        }
+        result |= (chunk & 0x1f) << (i * 5); // no shift overflow as i < 7
+        if chunk & 0x20 == 0 {
+            return Ok((result, i + 1, i == 0 || chunk != 0));
+        }
+    }
+    Err(DecodeErrorKind::Truncated)
+}
+
+/// Binary version of [`checked_varint32_decode5()`] for numbers written by `varint64_encode7()`.
+fn checked_varint64_decode7(polyline: &[u8]) -> Result<(u64, usize, bool), DecodeErrorKind> {
+    let mut result = 0;
+    for (i, &byte) in polyline.iter().enumerate() {
+        let chunk = byte as u64;
+        // 64 bits are stored in at most 10 bytes by 7 bits each, so the 10th byte can only be 0 or 1.
+        if i == 9 && chunk > 1 {
+            return Err(DecodeErrorKind::Overflow);
+        }
+        result |= (chunk & 0x7f) << (i * 7); // no shift overflow as i < 10
+        if chunk & 0x80 == 0 {
+            return Ok((result, i + 1, i == 0 || chunk != 0));
+        }
+    }
+    Err(DecodeErrorKind::Truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{encode, encode_binary};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn valid_points() {
+        let points = [
+            (55.58513, 12.99958),
+            (55.61461, 13.04627),
+            (-90.0, -180.0),
+            (90.0, 0.0),
+            (0.0, 180.0),
+        ];
+        for precision in 0..=7 {
+            assert_eq!(try_encode(precision, points), Ok(encode(precision, points)));
+            assert_eq!(
+                try_encode_binary(precision, points),
+                Ok(encode_binary(precision, points))
+            );
+        }
+        assert_eq!(try_encode(5, []), Ok(String::new()));
+    }
+
+    #[test]
+    fn invalid_points() {
+        fn err<T>(index: usize, kind: EncodeErrorKind) -> Result<T, EncodeError> {
+            Err(EncodeError { index, kind })
+        }
+        assert_eq!(
+            try_encode(5, [(f64::NAN, 0.0)]),
+            err(0, EncodeErrorKind::NaN)
+        );
+        assert_eq!(
+            try_encode(5, [(0.0, 0.0), (0.0, f64::NAN)]),
+            err(1, EncodeErrorKind::NaN)
+        );
+        assert_eq!(
+            try_encode(5, [(0.0, 0.0), (1.0, 1.0), (f64::NEG_INFINITY, 0.0)]),
+            err(2, EncodeErrorKind::Infinite)
+        );
+        assert_eq!(
+            try_encode(5, [(0.0, 180.00001)]),
+            err(0, EncodeErrorKind::OutOfRange)
+        );
+        assert_eq!(
+            try_encode_binary(5, [(-90.1, 0.0)]),
+            err(0, EncodeErrorKind::OutOfRange)
+        );
+
+        // Up to 180 * 10^7 fits into i32, but the change from -180 to 180 doesn't
+        assert_eq!(
+            try_encode(7, [(0.0, -180.0), (0.0, 180.0)]),
+            err(1, EncodeErrorKind::DeltaOverflow)
+        );
+        assert_eq!(
+            try_encode_with(5, RangePolicy::Allow, [(1e6, 0.0)]),
+            err(0, EncodeErrorKind::DeltaOverflow)
+        );
+        assert_eq!(
+            try_encode_with(5, RangePolicy::Allow, [(1000.0, -1000.0)]),
+            Ok(encode(5, [(1000.0, -1000.0)]))
+        );
+        // Each change fits into i32, but the accumulated coordinate doesn't
+        assert_eq!(
+            try_encode_with(5, RangePolicy::Allow, [(20000.0, 0.0), (40000.0, 0.0)]),
+            err(1, EncodeErrorKind::DeltaOverflow)
+        );
+        assert_eq!(
+            try_encode_binary_with(5, RangePolicy::Allow, [(0.0, -20000.0), (0.0, -40000.0)]),
+            err(1, EncodeErrorKind::DeltaOverflow)
+        );
+        let points = [(20000.0, 0.0), (21000.0, 0.0)];
+        let polyline = try_encode_with(5, RangePolicy::Allow, points).unwrap();
+        assert_eq!(
+            try_decode(5, &polyline).unwrap().collect::<Vec<_>>(),
+            points
+        );
+    }
+
+    #[test]
+    fn normalize() {
+        let normalize = |point| RangePolicy::Normalize.apply(point).unwrap();
+        assert_eq!(normalize((55.0, 13.0)), (55.0, 13.0));
+        assert_eq!(normalize((90.5, 180.0)), (90.0, 180.0));
+        assert_eq!(normalize((-100.0, -180.0)), (-90.0, -180.0));
+        assert_eq!(normalize((0.0, 190.0)), (0.0, -170.0));
+        assert_eq!(normalize((0.0, -190.0)), (0.0, 170.0));
+        assert_eq!(normalize((0.0, 540.0)), (0.0, -180.0));
+
+        assert_eq!(
+            try_encode_binary_with(6, RangePolicy::Normalize, [(0.0, 0.0), (91.0, 361.0)]),
+            Ok(encode_binary(6, [(0.0, 0.0), (90.0, 1.0)]))
+        );
+    }
+
+    #[test]
+    fn try_decode_valid() {
+        let polyline = "angrIk~inAgwDybH_|D_{KeoEwtLozFo`Gre@tcA";
+        let points: Vec<_> = crate::decode(5, polyline).collect();
+        for policy in [CanonicalPolicy::Accept, CanonicalPolicy::Reject] {
+            let iter = try_decode_with(5, policy, polyline).unwrap();
+            assert_eq!(iter.collect::<Vec<_>>(), points);
+            let binary = encode_binary(5, points.iter().copied());
+            let iter = try_decode_binary_with(5, policy, &binary).unwrap();
+            assert_eq!(iter.collect::<Vec<_>>(), points);
+            assert!(try_decode_with(5, policy, "").unwrap().next().is_none());
+        }
+
+        // Extreme values
+        let points = [(214.7483647, -214.7483648), (0.0, -0.0000001)];
+        assert!(try_decode(7, &encode(7, points)).is_ok());
+        assert!(try_decode_binary(7, &encode_binary(7, points)).is_ok());
+    }
+
+    #[test]
+    fn try_decode_invalid() {
+        fn err(offset: usize, kind: DecodeErrorKind) -> Option<DecodeError> {
+            Some(DecodeError { offset, kind })
+        }
+        assert_eq!(
+            try_decode(5, "angrIk~inA gwDybH").err(),
+            err(10, DecodeErrorKind::InvalidChar)
+        );
+        assert_eq!(
+            try_decode(5, "angrIk~inAgw\u{7f}DybH").err(),
+            err(10, DecodeErrorKind::InvalidChar)
+        );
+        assert_eq!(
+            try_decode(5, "angrIk~inAgwDyb").err(),
+            err(13, DecodeErrorKind::Truncated)
+        );
+        assert_eq!(
+            try_decode(5, "angrIk~inAgwD").err(),
+            err(13, DecodeErrorKind::Truncated)
+        );
+        // 7th chunk has more than 2 bits
+        assert_eq!(
+            try_decode(5, "??~~~~~~C?").err(),
+            err(2, DecodeErrorKind::Overflow)
+        );
+        // Each change fits, but their sum doesn't
+        let max = encode(0, [(i32::MAX as f64, 0.0)]);
+        assert!(try_decode(0, &max).is_ok());
+        assert_eq!(
+            try_decode(0, &max.repeat(2)).err(),
+            err(max.len(), DecodeErrorKind::Overflow)
+        );
+
+        let binary = encode_binary(5, [(55.58513, 12.99958), (55.61461, 13.04627)]);
+        assert_eq!(
+            try_decode_binary(5, &[0xFF; 10]).err(),
+            err(0, DecodeErrorKind::Overflow)
+        );
+        assert_eq!(
+            try_decode_binary(5, &[&binary[..], &[0x80]].concat()).err(),
+            err(binary.len(), DecodeErrorKind::Truncated)
+        );
+        let max = encode_binary(0, [(i32::MAX as f64, 0.0)]);
+        assert!(try_decode_binary(0, &max).is_ok());
+        assert_eq!(
+            try_decode_binary(0, &[&max[..], &max[..]].concat()).err(),
+            err(max.len(), DecodeErrorKind::Overflow)
+        );
+    }
+
+    #[test]
+    fn try_decode_non_canonical() {
+        let padded = "angri?k~inAgwDybH";
+        assert_eq!(
+            try_decode_with(5, CanonicalPolicy::Accept, padded)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            crate::decode(5, "angrIk~inAgwDybH").collect::<Vec<_>>()
+        );
+        assert_eq!(
+            try_decode_with(5, CanonicalPolicy::Reject, padded).err(),
+            Some(DecodeError {
+                offset: 0,
+                kind: DecodeErrorKind::NonCanonical
+            })
+        );
+        assert_eq!(
+            try_decode_with(5, CanonicalPolicy::Reject, "??_?").err(),
+            Some(DecodeError {
+                offset: 2,
+                kind: DecodeErrorKind::NonCanonical
+            })
+        );
+
+        let padded = [0x00, 0x81, 0x80, 0x00];
+        assert!(try_decode_binary(5, &padded).is_ok());
+        assert_eq!(
+            try_decode_binary_with(5, CanonicalPolicy::Reject, &padded).err(),
+            Some(DecodeError {
+                offset: 1,
+                kind: DecodeErrorKind::NonCanonical
+            })
+        );
+    }
+
+    #[test]
+    fn display() {
+        let error = EncodeError {
+            index: 3,
+            kind: EncodeErrorKind::NaN,
+        };
+        assert_eq!(
+            error.to_string(),
+            "can't encode point #3: coordinate is NaN"
+        );
+        assert_eq!(
+            DecodeError {
+                offset: 10,
+                kind: DecodeErrorKind::Truncated,
+            }
+            .to_string(),
+            "can't decode number at offset 10: unexpected end of input"
+        );
+    }
+}
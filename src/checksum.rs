@@ -0,0 +1,170 @@
+//! Binary polylines with a trailing CRC32C checksum, which detects corrupted data before any point is decoded.
+
+use crate::{BinaryPolylineIter, encode_binary};
+
+/// CRC32C (Castagnoli) polynomial in the reversed bit order.
+const CRC32C_POLYNOMIAL: u32 = 0x82F6_3B78;
+
+const CRC32C_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ CRC32C_POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Error returned by [`decode_binary_checked()`] when the data doesn't match its checksum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumError {
+    /// The data is shorter than the 4-byte checksum.
+    Truncated,
+    /// The checksum stored in the data differs from the one computed over the points.
+    Mismatch { expected: u32, actual: u32 },
+}
+
+impl std::fmt::Display for ChecksumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChecksumError::Truncated => {
+                write!(f, "binary polyline is too short to have a checksum")
+            }
+            ChecksumError::Mismatch { expected, actual } => write!(
+                f,
+                "binary polyline checksum mismatch: expected {expected:#010x}, got {actual:#010x}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ChecksumError {}
+
+/// Encodes points with [`encode_binary()`] and appends the CRC32C checksum of the result
+/// as 4 little-endian bytes.
+///
+/// ```
+/// let points = [(55.58513, 12.99958), (55.61461, 13.04627)];
+/// let binary = polyline_iter::encode_binary_checked(5, points);
+/// assert_eq!(binary[..binary.len() - 4], polyline_iter::encode_binary(5, points));
+/// ```
+pub fn encode_binary_checked(
+    precision: u8,
+    points: impl IntoIterator<Item = (f64, f64)>,
+) -> Vec<u8> {
+    let mut result = encode_binary(precision, points);
+    let checksum = crc32c(&result);
+    result.extend_from_slice(&checksum.to_le_bytes());
+    result
+}
+
+/// Verifies the checksum written by [`encode_binary_checked()`] and returns an iterator over the points.
+/// As every point is stored relative to the previous one, a single flipped bit changes all the following
+/// points, so the whole data is verified before decoding.
+///
+/// ```
+/// use polyline_iter::ChecksumError;
+///
+/// let points = [(55.58513, 12.99958), (55.61461, 13.04627)];
+/// let mut binary = polyline_iter::encode_binary_checked(5, points);
+/// assert_eq!(polyline_iter::decode_binary_checked(5, &binary).unwrap().collect::<Vec<_>>(), points);
+///
+/// binary[2] ^= 0x10;
+/// assert!(matches!(polyline_iter::decode_binary_checked(5, &binary), Err(ChecksumError::Mismatch { .. })));
+/// ```
+pub fn decode_binary_checked(
+    precision: u8,
+    data: &[u8],
+) -> Result<BinaryPolylineIter<'_>, ChecksumError> {
+    let Some((polyline, checksum)) = data.split_last_chunk::<4>() else {
+        return Err(ChecksumError::Truncated);
+    };
+    let expected = u32::from_le_bytes(*checksum);
+    let actual = crc32c(polyline);
+    if expected != actual {
+        return Err(ChecksumError::Mismatch { expected, actual });
+    }
+    Ok(BinaryPolylineIter::new(precision, polyline))
+}
+
+/// Table-driven CRC32C, compatible with the `crc32c` crate and SSE 4.2 `crc32` instruction.
+fn crc32c(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc = CRC32C_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn crc32c_check_values() {
+        assert_eq!(crc32c(b""), 0);
+        assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+        assert_eq!(crc32c(&[0; 32]), 0x8A91_36AA);
+        assert_eq!(crc32c(&[0xFF; 32]), 0x62A8_AB43);
+    }
+
+    #[test]
+    fn round_trip() {
+        let points: Vec<_> = decode(5, "angrIk~inAgwDybH_|D_{KeoEwtLozFo`Gre@tcA").collect();
+        let binary = encode_binary_checked(5, points.iter().copied());
+        assert_eq!(
+            decode_binary_checked(5, &binary)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            points
+        );
+
+        let empty = encode_binary_checked(5, []);
+        assert_eq!(empty, [0; 4]);
+        assert_eq!(decode_binary_checked(5, &empty).unwrap().next(), None);
+    }
+
+    #[test]
+    fn corrupted() {
+        let points: Vec<_> = decode(5, "angrIk~inAgwDybH_|D_{KeoEwtLozFo`Gre@tcA").collect();
+        let binary = encode_binary_checked(5, points);
+
+        // Every single flipped bit is detected, including bits of the checksum itself
+        for i in 0..binary.len() * 8 {
+            let mut corrupted = binary.clone();
+            corrupted[i / 8] ^= 1 << (i % 8);
+            assert!(matches!(
+                decode_binary_checked(5, &corrupted),
+                Err(ChecksumError::Mismatch { .. })
+            ));
+        }
+
+        assert_eq!(
+            decode_binary_checked(5, &binary[..binary.len() - 1]).err(),
+            Some(ChecksumError::Mismatch {
+                expected: u32::from_le_bytes(
+                    binary[binary.len() - 5..binary.len() - 1]
+                        .try_into()
+                        .unwrap()
+                ),
+                actual: crc32c(&binary[..binary.len() - 5]),
+            })
+        );
+        assert_eq!(
+            decode_binary_checked(5, &[1, 2, 3]).err(),
+            Some(ChecksumError::Truncated)
+        );
+    }
+}
@@ -0,0 +1,549 @@
+//! Esri ArcGIS `CompressedGeometry` format, returned by route and directions services.
+//!
+//! The string is a sequence of signed base-32 numbers like `+1m91-6fkfr+202tp+k+f`. The first one is
+//! the multiplier, and the rest are x/y deltas multiplied by it. The newer variant starts with `+0+1`,
+//! followed by flags with the presence of Z and M values, their multipliers and the XY section,
+//! while Z and M deltas are stored in separate sections after a `|` delimiter.
+//!
+//! Esri geometries are `(x, y)`, which this module maps to `(lat, lon) = (y, x)` to match the rest of the crate.
+//!
+//! ```
+//! use polyline_iter::esri;
+//!
+//! let polyline = esri::encode(100_000, [(37.78289, -122.40636), (37.78309, -122.40646)]);
+//! assert_eq!(polyline, "+31l0-blhns+3j9nh-a+k");
+//!
+//! // Convert to Google's polyline
+//! let iter = esri::decode(&polyline).unwrap();
+//! assert_eq!(polyline_iter::encode(5, iter.map(Into::into)), "anreFvnbjVg@R");
+//! ```
+
+/// Flag of the newer format indicating that points have Z values.
+const HAS_Z: i64 = 0x1;
+/// Flag of the newer format indicating that points have M values.
+const HAS_M: i64 = 0x2;
+
+/// Point decoded from a `CompressedGeometry` string.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    /// Y coordinate, which is latitude for geographic coordinate systems.
+    pub lat: f64,
+    /// X coordinate, which is longitude for geographic coordinate systems.
+    pub lon: f64,
+    /// Z value, if present.
+    pub z: Option<f64>,
+    /// M value, if present.
+    pub m: Option<f64>,
+}
+
+impl From<Point> for (f64, f64) {
+    fn from(point: Point) -> Self {
+        (point.lat, point.lon)
+    }
+}
+
+/// Error returned by [`decode()`] when the header is malformed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderError {
+    /// The string doesn't start with a valid multiplier.
+    Malformed,
+    /// The newer format has an unsupported version.
+    UnsupportedVersion(i64),
+    /// The newer format has flags other than the presence of Z and M values.
+    UnsupportedFlags(i64),
+}
+
+impl std::fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeaderError::Malformed => write!(f, "malformed compressed geometry header"),
+            HeaderError::UnsupportedVersion(version) => {
+                write!(f, "unsupported compressed geometry version {version}")
+            }
+            HeaderError::UnsupportedFlags(flags) => {
+                write!(f, "unsupported compressed geometry flags {flags:#x}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HeaderError {}
+
+/// Iterator over points decoded from a `CompressedGeometry` string, created by [`decode()`].
+///
+/// Just like [`PolylineIter`](crate::PolylineIter), stops on the first malformed point.
+pub struct CompressedGeometryIter<'a> {
+    xy: &'a [u8],
+    z: Option<&'a [u8]>,
+    m: Option<&'a [u8]>,
+    xy_scale: f64,
+    z_scale: f64,
+    m_scale: f64,
+    /// Last processed x, multiplied by the scale.
+    x: i64,
+    /// Last processed y, multiplied by the scale.
+    y: i64,
+    /// Last processed z, multiplied by the z scale.
+    last_z: i64,
+    /// Last processed m, multiplied by the m scale.
+    last_m: i64,
+}
+
+impl<'a> CompressedGeometryIter<'a> {
+    /// Parses the header and creates a new iterator over the points that follow it.
+    pub fn new(polyline: &'a str) -> Result<Self, HeaderError> {
+        let mut sections = polyline.split('|').map(str::as_bytes);
+        let mut xy = sections.next().unwrap_or_default();
+
+        let first = read_number(&mut xy).ok_or(HeaderError::Malformed)?;
+        if first != 0 {
+            // Classic format, where the first number is the multiplier
+            if first < 0 {
+                return Err(HeaderError::Malformed);
+            }
+            return Ok(Self::with_sections(xy, None, None, first as f64, 1.0, 1.0));
+        }
+
+        let version = read_number(&mut xy).ok_or(HeaderError::Malformed)?;
+        if version != 1 {
+            return Err(HeaderError::UnsupportedVersion(version));
+        }
+        let flags = read_number(&mut xy).ok_or(HeaderError::Malformed)?;
+        if flags & !(HAS_Z | HAS_M) != 0 {
+            return Err(HeaderError::UnsupportedFlags(flags));
+        }
+        let xy_scale = Self::read_multiplier(&mut xy)?;
+        let z_scale = if flags & HAS_Z != 0 {
+            Self::read_multiplier(&mut xy)?
+        } else {
+            1.0
+        };
+        let m_scale = if flags & HAS_M != 0 {
+            Self::read_multiplier(&mut xy)?
+        } else {
+            1.0
+        };
+        let z = (flags & HAS_Z != 0).then(|| sections.next().unwrap_or_default());
+        let m = (flags & HAS_M != 0).then(|| sections.next().unwrap_or_default());
+        Ok(Self::with_sections(xy, z, m, xy_scale, z_scale, m_scale))
+    }
+
+    fn read_multiplier(xy: &mut &[u8]) -> Result<f64, HeaderError> {
+        match read_number(xy) {
+            Some(multiplier) if multiplier > 0 => Ok(multiplier as f64),
+            _ => Err(HeaderError::Malformed),
+        }
+    }
+
+    fn with_sections(
+        xy: &'a [u8],
+        z: Option<&'a [u8]>,
+        m: Option<&'a [u8]>,
+        xy_scale: f64,
+        z_scale: f64,
+        m_scale: f64,
+    ) -> Self {
+        CompressedGeometryIter {
+            xy,
+            z,
+            m,
+            xy_scale,
+            z_scale,
+            m_scale,
+            x: 0,
+            y: 0,
+            last_z: 0,
+            last_m: 0,
+        }
+    }
+
+    /// Returns `true` if points have Z values.
+    pub fn has_z(&self) -> bool {
+        self.z.is_some()
+    }
+
+    /// Returns `true` if points have M values.
+    pub fn has_m(&self) -> bool {
+        self.m.is_some()
+    }
+
+    /// O(n) operation to count the number of points in the polyline without consuming the iterator.
+    pub fn len(&self) -> usize {
+        self.xy.iter().filter(|&&byte| is_sign(byte)).count() / 2
+    }
+
+    /// Checks if the polyline contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Iterator for CompressedGeometryIter<'_> {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x_change = read_number(&mut self.xy)?;
+        let y_change = read_number(&mut self.xy)?;
+        let z = match &mut self.z {
+            Some(section) => {
+                self.last_z = self.last_z.wrapping_add(read_number(section)?);
+                Some(self.last_z as f64 / self.z_scale)
+            }
+            None => None,
+        };
+        let m = match &mut self.m {
+            Some(section) => {
+                self.last_m = self.last_m.wrapping_add(read_number(section)?);
+                Some(self.last_m as f64 / self.m_scale)
+            }
+            None => None,
+        };
+        // Wrapping to avoid overflow panics on malformed input
+        self.x = self.x.wrapping_add(x_change);
+        self.y = self.y.wrapping_add(y_change);
+        Some(Point {
+            lat: self.y as f64 / self.xy_scale,
+            lon: self.x as f64 / self.xy_scale,
+            z,
+            m,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each number takes from 2 to 14 chars.
+        let len = self.xy.len();
+        (len / 28, Some(len / 4))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+/// Decodes the header of a `CompressedGeometry` string and returns an iterator over its points.
+///
+/// ```
+/// let mut iter = polyline_iter::esri::decode("+0+1+3+1+a+1+a-4+0+1|+88+a|+1+1").unwrap();
+/// assert!(iter.has_z() && iter.has_m());
+/// let point = iter.next().unwrap();
+/// assert_eq!((point.lat, point.lon, point.z, point.m), (-4.0, 10.0, Some(26.4), Some(1.0)));
+/// ```
+pub fn decode(polyline: &str) -> Result<CompressedGeometryIter<'_>, HeaderError> {
+    CompressedGeometryIter::new(polyline)
+}
+
+/// Encodes `(lat, lon)` points into the classic `CompressedGeometry` format with the given multiplier,
+/// e.g. `100_000` for 5 decimal places.
+pub fn encode(multiplier: u32, points: impl IntoIterator<Item = (f64, f64)>) -> String {
+    assert!(multiplier > 0, "multiplier must be positive");
+    let mut result = String::with_capacity(16);
+    write_number(multiplier as i64, &mut result);
+    write_xy(multiplier as f64, points, &mut result);
+    result
+}
+
+/// Encodes points into the newer `CompressedGeometry` format with optional Z and M values.
+/// Z or M section is written if the corresponding multiplier is present, in which case
+/// missing values of points are written as zeros.
+///
+/// ```
+/// use polyline_iter::esri::{self, Point};
+///
+/// let points = [
+///     Point { lat: -4.0, lon: 10.0, z: Some(26.4), m: Some(1.0) },
+///     Point { lat: -3.0, lon: 10.0, z: Some(27.4), m: Some(2.0) },
+/// ];
+/// let polyline = esri::encode_zm(1, Some(10), Some(1), points);
+/// assert_eq!(polyline, "+0+1+3+1+a+1+a-4+0+1|+88+a|+1+1");
+/// assert_eq!(esri::decode(&polyline).unwrap().collect::<Vec<_>>(), points);
+/// ```
+pub fn encode_zm(
+    xy_multiplier: u32,
+    z_multiplier: Option<u32>,
+    m_multiplier: Option<u32>,
+    points: impl IntoIterator<Item = Point>,
+) -> String {
+    assert!(xy_multiplier > 0, "multiplier must be positive");
+    assert!(
+        z_multiplier.is_none_or(|multiplier| multiplier > 0),
+        "multiplier must be positive"
+    );
+    assert!(
+        m_multiplier.is_none_or(|multiplier| multiplier > 0),
+        "multiplier must be positive"
+    );
+
+    let mut result = String::with_capacity(16);
+    write_number(0, &mut result);
+    write_number(1, &mut result);
+    let flags = z_multiplier.map_or(0, |_| HAS_Z) | m_multiplier.map_or(0, |_| HAS_M);
+    write_number(flags, &mut result);
+    write_number(xy_multiplier as i64, &mut result);
+    for multiplier in [z_multiplier, m_multiplier].into_iter().flatten() {
+        write_number(multiplier as i64, &mut result);
+    }
+
+    let mut z_section = String::new();
+    let mut m_section = String::new();
+    let mut prev = (0, 0);
+    let xy = points.into_iter().map(|point| {
+        if let Some(multiplier) = z_multiplier {
+            let z = (point.z.unwrap_or_default() * multiplier as f64).round() as i64;
+            write_number(z.wrapping_sub(prev.0), &mut z_section);
+            prev.0 = z;
+        }
+        if let Some(multiplier) = m_multiplier {
+            let m = (point.m.unwrap_or_default() * multiplier as f64).round() as i64;
+            write_number(m.wrapping_sub(prev.1), &mut m_section);
+            prev.1 = m;
+        }
+        (point.lat, point.lon)
+    });
+    write_xy(xy_multiplier as f64, xy, &mut result);
+
+    if z_multiplier.is_some() {
+        result.push('|');
+        result.push_str(&z_section);
+    }
+    if m_multiplier.is_some() {
+        result.push('|');
+        result.push_str(&m_section);
+    }
+    result
+}
+
+fn write_xy(scale: f64, points: impl IntoIterator<Item = (f64, f64)>, buffer: &mut String) {
+    // Deltas are computed on already scaled values, as the decoder sums up integers.
+    let mut prev = (0, 0);
+    for (lat, lon) in points {
+        let x = (lon * scale).round() as i64;
+        let y = (lat * scale).round() as i64;
+        // Wrapping as in the decoder, as coordinates that don't fit into i64 are saturated
+        write_number(x.wrapping_sub(prev.0), buffer);
+        write_number(y.wrapping_sub(prev.1), buffer);
+        prev = (x, y);
+    }
+}
+
+fn is_sign(byte: u8) -> bool {
+    byte == b'+' || byte == b'-'
+}
+
+/// Writes a signed base-32 number with digits `0-9a-v`, e.g. `+1m91` or `-6fkfr`.
+fn write_number(value: i64, buffer: &mut String) {
+    buffer.push(if value < 0 { '-' } else { '+' });
+    let mut value = value.unsigned_abs();
+    let mut digits = [0u8; 13];
+    let mut len = 0;
+    loop {
+        digits[len] = b"0123456789abcdefghijklmnopqrstuv"[(value & 0x1F) as usize];
+        len += 1;
+        value >>= 5;
+        if value == 0 {
+            break;
+        }
+    }
+    buffer.extend(digits[..len].iter().rev().map(|&digit| digit as char));
+}
+
+/// Reads a signed base-32 number, returning `None` if it's missing or malformed.
+fn read_number(polyline: &mut &[u8]) -> Option<i64> {
+    let (&sign, rest) = polyline.split_first()?;
+    if !is_sign(sign) {
+        return None;
+    }
+    let len = rest.iter().take_while(|&&byte| !is_sign(byte)).count();
+    // 13 digits by 5 bits each are enough for any i64
+    if len == 0 || len > 13 {
+        return None;
+    }
+    let mut value: u64 = 0;
+    for &byte in &rest[..len] {
+        let digit = match byte {
+            b'0'..=b'9' => byte - b'0',
+            b'a'..=b'v' => byte - b'a' + 10,
+            b'A'..=b'V' => byte - b'A' + 10,
+            _ => return None,
+        };
+        value = (value << 5) | digit as u64;
+    }
+    *polyline = &rest[len..];
+    let value = value as i64;
+    Some(if sign == b'-' {
+        value.wrapping_neg()
+    } else {
+        value
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn numbers() {
+        for value in [0, 1, -1, 31, 32, -32, 55585, -6803963, 2100153, i64::MAX] {
+            let mut buffer = String::new();
+            write_number(value, &mut buffer);
+            assert_eq!(read_number(&mut buffer.as_bytes()), Some(value));
+        }
+        let mut buffer = String::new();
+        write_number(55585, &mut buffer);
+        write_number(-6803963, &mut buffer);
+        assert_eq!(buffer, "+1m91-6fkfr");
+
+        assert_eq!(read_number(&mut "".as_bytes()), None);
+        assert_eq!(read_number(&mut "+".as_bytes()), None);
+        assert_eq!(read_number(&mut "1m91".as_bytes()), None);
+        assert_eq!(read_number(&mut "+1w".as_bytes()), None);
+        assert_eq!(read_number(&mut "+1M91".as_bytes()), Some(55585));
+    }
+
+    #[test]
+    fn classic() {
+        // San Francisco, as returned by ArcGIS route service
+        let mut iter = decode("+1m91-6fkfr+202tp+k+f").unwrap();
+        assert!(!iter.has_z() && !iter.has_m());
+        assert_eq!(iter.len(), 2);
+        let (y, x) = (2100153.0, -6803963.0);
+        assert_eq!(
+            iter.next(),
+            Some(Point {
+                lat: y / 55585.0,
+                lon: x / 55585.0,
+                z: None,
+                m: None
+            })
+        );
+        assert_eq!(
+            iter.next().map(<(f64, f64)>::from),
+            Some(((y + 15.0) / 55585.0, (x + 20.0) / 55585.0))
+        );
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn zm() {
+        // Worked out by hand from the format description: flags `+3` for Z and M, multipliers 55585,
+        // 1000 (`v8`) and 10, then Z changes 10104 (`9ro`) and -92 (`2s`), and M changes 1 and -1
+        let polyline = "+0+1+3+1m91+v8+a-6fkfr+202tp+k+f|+9ro-2s|+1-1";
+        let (y, x) = (2100153.0, -6803963.0);
+        let points = [
+            Point {
+                lat: y / 55585.0,
+                lon: x / 55585.0,
+                z: Some(10.104),
+                m: Some(0.1),
+            },
+            Point {
+                lat: (y + 15.0) / 55585.0,
+                lon: (x + 20.0) / 55585.0,
+                z: Some(10.012),
+                m: Some(0.0),
+            },
+        ];
+        let iter = decode(polyline).unwrap();
+        assert!(iter.has_z() && iter.has_m());
+        assert_eq!(iter.collect::<Vec<_>>(), points);
+        assert_eq!(encode_zm(55585, Some(1000), Some(10), points), polyline);
+    }
+
+    #[test]
+    fn saturated_coordinates() {
+        // Values that don't fit into i64 are saturated, and the changes between them wrap as in the decoder
+        let polyline = encode(1, [(1e300, -1e300), (-1e300, 1e300)]);
+        let points: Vec<_> = decode(&polyline).unwrap().map(<(f64, f64)>::from).collect();
+        assert_eq!(
+            points,
+            [
+                (i64::MAX as f64, i64::MIN as f64),
+                (i64::MIN as f64, i64::MAX as f64)
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let points = [
+            (55.58513, 12.99958),
+            (55.61461, 13.04627),
+            (-54.90653, -65.99208),
+            (-37.88209, 144.79631),
+        ];
+        let polyline = encode(100_000, points);
+        let iter = decode(&polyline).unwrap();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.map(<(f64, f64)>::from).collect::<Vec<_>>(), points);
+
+        let points = points.map(|(lat, lon)| Point {
+            lat,
+            lon,
+            z: Some(lat.abs().floor() + 0.5),
+            m: Some(lon.abs().floor()),
+        });
+        for (z, m) in [
+            (None, None),
+            (Some(10_000), None),
+            (None, Some(100_000)),
+            (Some(10_000), Some(100_000)),
+        ] {
+            let polyline = encode_zm(100_000, z, m, points);
+            let iter = decode(&polyline).unwrap();
+            assert_eq!((iter.has_z(), iter.has_m()), (z.is_some(), m.is_some()));
+            let decoded: Vec<_> = iter.collect();
+            assert_eq!(decoded.len(), points.len());
+            for (decoded, original) in decoded.iter().zip(points) {
+                assert_eq!((decoded.lat, decoded.lon), (original.lat, original.lon));
+                assert_eq!(decoded.z, z.map(|_| original.z.unwrap()));
+                assert_eq!(decoded.m, m.map(|_| original.m.unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    fn broken_string() {
+        assert_eq!(decode("").err(), Some(HeaderError::Malformed));
+        assert_eq!(decode("1m91").err(), Some(HeaderError::Malformed));
+        assert_eq!(decode("-1m91").err(), Some(HeaderError::Malformed));
+        assert_eq!(
+            decode("+0+2+0+1").err(),
+            Some(HeaderError::UnsupportedVersion(2))
+        );
+        assert_eq!(decode("+0+1+1+a").err(), Some(HeaderError::Malformed));
+        assert_eq!(
+            decode("+0+1+7+1+1+1").err(),
+            Some(HeaderError::UnsupportedFlags(7))
+        );
+        assert_eq!(
+            decode("+0+1-1+1").err(),
+            Some(HeaderError::UnsupportedFlags(-1))
+        );
+
+        // Incomplete last point
+        assert_eq!(decode("+1m91-6fkfr+202tp+k").unwrap().count(), 1);
+        assert_eq!(
+            decode("+1m91-6fkfr+202tp+k")
+                .unwrap()
+                .collect::<Vec<_>>()
+                .len(),
+            1
+        );
+        // Invalid digit
+        assert_eq!(
+            decode("+1m91-6fkfr+202tp+k+z")
+                .unwrap()
+                .collect::<Vec<_>>()
+                .len(),
+            1
+        );
+        // Missing Z value of the second point
+        assert_eq!(
+            decode("+0+1+1+1+1+1+1+1+1|+1")
+                .unwrap()
+                .collect::<Vec<_>>()
+                .len(),
+            1
+        );
+    }
+}
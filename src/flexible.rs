@@ -0,0 +1,526 @@
+//! [HERE Flexible Polyline](https://github.com/heremaps/flexible-polyline) format.
+//!
+//! Similar to Google's polyline, the format stores zigzag-encoded deltas as 5-bit varints, but uses
+//! URL-safe base64 alphabet, starts with a header with the precision and supports an optional third dimension.
+//!
+//! ```
+//! use polyline_iter::flexible::{self, ThirdDimension};
+//!
+//! let iter = flexible::decode("BFoz5xJ67i1B1B7PzIhaxL7Y").unwrap();
+//! assert_eq!(iter.header().precision, 5);
+//! assert_eq!(iter.header().third_dim, ThirdDimension::Absent);
+//! assert_eq!(
+//!     iter.collect::<Vec<_>>(),
+//!     [
+//!         (50.10228, 8.69821, None),
+//!         (50.10201, 8.69567, None),
+//!         (50.10063, 8.69150, None),
+//!         (50.09878, 8.68752, None),
+//!     ]
+//! );
+//!
+//! let points = [(50.10228, 8.69821), (50.10201, 8.69567), (50.10063, 8.69150), (50.09878, 8.68752)];
+//! assert_eq!(flexible::encode(5, points), "BFoz5xJ67i1B1B7PzIhaxL7Y");
+//! ```
+
+use crate::base64url::{DECODING_TABLE, ENCODING_TABLE};
+use crate::polyline64::{zigzag64_decode, zigzag64_encode};
+
+/// The only format version defined by the specification.
+const FORMAT_VERSION: u64 = 1;
+
+/// Meaning of the optional third dimension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThirdDimension {
+    Absent = 0,
+    Level = 1,
+    Altitude = 2,
+    Elevation = 3,
+    // 4 and 5 are reserved by the specification
+    Custom1 = 6,
+    Custom2 = 7,
+}
+
+impl ThirdDimension {
+    fn from_bits(bits: u64) -> Option<Self> {
+        match bits {
+            0 => Some(ThirdDimension::Absent),
+            1 => Some(ThirdDimension::Level),
+            2 => Some(ThirdDimension::Altitude),
+            3 => Some(ThirdDimension::Elevation),
+            6 => Some(ThirdDimension::Custom1),
+            7 => Some(ThirdDimension::Custom2),
+            _ => None,
+        }
+    }
+}
+
+/// Metadata stored at the beginning of every flexible polyline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    /// Number of decimal places of latitude and longitude, up to 15.
+    pub precision: u8,
+    /// Meaning of the third dimension, if any.
+    pub third_dim: ThirdDimension,
+    /// Number of decimal places of the third dimension, up to 15.
+    pub third_dim_precision: u8,
+}
+
+/// Error returned by [`decode()`] when the header is malformed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderError {
+    /// The polyline is empty or contains characters outside of the URL-safe base64 alphabet.
+    Malformed,
+    /// The format version is not supported.
+    UnsupportedVersion(u64),
+    /// The third dimension uses a reserved value.
+    ReservedThirdDimension,
+}
+
+impl std::fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeaderError::Malformed => write!(f, "malformed flexible polyline header"),
+            HeaderError::UnsupportedVersion(version) => {
+                write!(f, "unsupported flexible polyline version {version}")
+            }
+            HeaderError::ReservedThirdDimension => {
+                write!(f, "flexible polyline uses reserved third dimension")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HeaderError {}
+
+/// Iterator over points decoded from a flexible polyline, created by [`decode()`].
+///
+/// Yields `(lat, lon, z)` where `z` is `None` if the polyline has no third dimension.
+/// Just like [`PolylineIter`](crate::PolylineIter), stops on the first malformed point.
+pub struct FlexiblePolylineIter<'a> {
+    polyline: &'a [u8],
+    header: Header,
+    scale: f64,
+    third_dim_scale: f64,
+    /// Last processed latitude, multiplied by the scale.
+    lat: i64,
+    /// Last processed longitude, multiplied by the scale.
+    lon: i64,
+    /// Last processed third dimension value, multiplied by the third dimension scale.
+    z: i64,
+}
+
+impl<'a> FlexiblePolylineIter<'a> {
+    /// Parses the header and creates a new iterator over the points that follow it.
+    pub fn new(polyline: &'a str) -> Result<Self, HeaderError> {
+        let mut polyline = polyline.as_bytes();
+        let version = varint_decode(&mut polyline).ok_or(HeaderError::Malformed)?;
+        if version != FORMAT_VERSION {
+            return Err(HeaderError::UnsupportedVersion(version));
+        }
+        let content = varint_decode(&mut polyline).ok_or(HeaderError::Malformed)?;
+        if content >> 11 != 0 {
+            return Err(HeaderError::Malformed);
+        }
+        let header = Header {
+            precision: (content & 0x0F) as u8,
+            third_dim: ThirdDimension::from_bits((content >> 4) & 0x07)
+                .ok_or(HeaderError::ReservedThirdDimension)?,
+            third_dim_precision: ((content >> 7) & 0x0F) as u8,
+        };
+        Ok(FlexiblePolylineIter {
+            polyline,
+            header,
+            scale: 10.0_f64.powi(header.precision as i32),
+            third_dim_scale: 10.0_f64.powi(header.third_dim_precision as i32),
+            lat: 0,
+            lon: 0,
+            z: 0,
+        })
+    }
+
+    /// Returns the metadata from the polyline header.
+    pub fn header(&self) -> Header {
+        self.header
+    }
+
+    fn dimensions(&self) -> usize {
+        if self.header.third_dim == ThirdDimension::Absent {
+            2
+        } else {
+            3
+        }
+    }
+
+    /// O(n) operation to count the number of points in the polyline without consuming the iterator.
+    pub fn len(&self) -> usize {
+        self.polyline
+            .iter()
+            .filter(|&&byte| DECODING_TABLE[byte as usize] & 0x20 == 0)
+            .count()
+            / self.dimensions()
+    }
+
+    /// Checks if the polyline contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Iterator for FlexiblePolylineIter<'_> {
+    type Item = (f64, f64, Option<f64>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let lat_change = varint_decode(&mut self.polyline)?;
+        let lon_change = varint_decode(&mut self.polyline)?;
+        let z = if self.header.third_dim != ThirdDimension::Absent {
+            let z_change = varint_decode(&mut self.polyline)?;
+            self.z = self.z.wrapping_add(zigzag64_decode(z_change));
+            Some(self.z as f64 / self.third_dim_scale)
+        } else {
+            None
+        };
+        self.lat = self.lat.wrapping_add(zigzag64_decode(lat_change));
+        self.lon = self.lon.wrapping_add(zigzag64_decode(lon_change));
+        Some((
+            self.lat as f64 / self.scale,
+            self.lon as f64 / self.scale,
+            z,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each number takes from 1 to 13 chars.
+        let len = self.polyline.len() / self.dimensions();
+        (len / 13, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+/// Decodes the header of a flexible polyline and returns an iterator over its points.
+///
+/// ```
+/// use polyline_iter::flexible::{self, ThirdDimension};
+///
+/// let iter = flexible::decode("BlBoz5xJ67i1BU1B7PUzIhaUxL7YU").unwrap();
+/// assert_eq!(iter.header().third_dim, ThirdDimension::Altitude);
+/// assert_eq!(iter.len(), 4);
+///
+/// let points: Vec<_> = iter.collect();
+/// assert_eq!(points[0], (50.10228, 8.69821, Some(10.0)));
+/// assert_eq!(points[3], (50.09878, 8.68752, Some(40.0)));
+///
+/// assert!(flexible::decode("").is_err());
+/// ```
+pub fn decode(polyline: &str) -> Result<FlexiblePolylineIter<'_>, HeaderError> {
+    FlexiblePolylineIter::new(polyline)
+}
+
+/// Encodes 2D points into a flexible polyline with the given precision, up to 15.
+pub fn encode(precision: u8, points: impl IntoIterator<Item = (f64, f64)>) -> String {
+    let header = Header {
+        precision,
+        third_dim: ThirdDimension::Absent,
+        third_dim_precision: 0,
+    };
+    encode_with_header(header, points.into_iter().map(|(lat, lon)| (lat, lon, 0.0)))
+}
+
+/// Encodes 3D points into a flexible polyline.
+///
+/// ```
+/// use polyline_iter::flexible::{self, ThirdDimension};
+///
+/// let points = [
+///     (50.1022829, 8.6982122, 10.0),
+///     (50.1020076, 8.6956695, 20.0),
+///     (50.1006313, 8.6914960, 30.0),
+///     (50.0987800, 8.6875156, 40.0),
+/// ];
+/// assert_eq!(
+///     flexible::encode_3d(5, ThirdDimension::Altitude, 0, points),
+///     "BlBoz5xJ67i1BU1B7PUzIhaUxL7YU"
+/// );
+/// ```
+pub fn encode_3d(
+    precision: u8,
+    third_dim: ThirdDimension,
+    third_dim_precision: u8,
+    points: impl IntoIterator<Item = (f64, f64, f64)>,
+) -> String {
+    let header = Header {
+        precision,
+        third_dim,
+        third_dim_precision,
+    };
+    encode_with_header(header, points)
+}
+
+/// Encodes points with the given header. The third coordinate is ignored if the third dimension is absent.
+fn encode_with_header(header: Header, points: impl IntoIterator<Item = (f64, f64, f64)>) -> String {
+    assert!(header.precision <= 15, "precision must be up to 15");
+    assert!(
+        header.third_dim_precision <= 15,
+        "third dimension precision must be up to 15"
+    );
+
+    let mut result = String::with_capacity(16);
+    varint_encode(FORMAT_VERSION, &mut result);
+    let content = header.precision as u64
+        | (header.third_dim as u64) << 4
+        | (header.third_dim_precision as u64) << 7;
+    varint_encode(content, &mut result);
+
+    let scale = 10.0_f64.powi(header.precision as i32);
+    let third_dim_scale = 10.0_f64.powi(header.third_dim_precision as i32);
+    let mut prev = (0, 0, 0);
+    for (lat, lon, z) in points {
+        // Deltas are computed on already scaled values, as in the reference implementation.
+        let lat = (lat * scale).round() as i64;
+        let lon = (lon * scale).round() as i64;
+        varint_encode(zigzag64_encode(lat.wrapping_sub(prev.0)), &mut result);
+        varint_encode(zigzag64_encode(lon.wrapping_sub(prev.1)), &mut result);
+        if header.third_dim != ThirdDimension::Absent {
+            let z = (z * third_dim_scale).round() as i64;
+            varint_encode(zigzag64_encode(z.wrapping_sub(prev.2)), &mut result);
+            prev.2 = z;
+        }
+        (prev.0, prev.1) = (lat, lon);
+    }
+    result
+}
+
+/// Same as [`varint64_encode5`](crate::polyline64), but with URL-safe base64 alphabet.
+fn varint_encode(mut value: u64, buffer: &mut String) {
+    while value >= 0x20 {
+        buffer.push(ENCODING_TABLE[((value & 0x1F) | 0x20) as usize] as char);
+        value >>= 5;
+    }
+    buffer.push(ENCODING_TABLE[value as usize] as char);
+}
+
+fn varint_decode(polyline: &mut &[u8]) -> Option<u64> {
+    let mut result = 0;
+    // 64 bits are stored in at most 13 chars by 5 bits each
+    for i in 0..polyline.len().min(13) {
+        let chunk = DECODING_TABLE[polyline[i] as usize];
+        if chunk == 0xFF {
+            return None;
+        }
+        result |= ((chunk & 0x1f) as u64) << (i * 5); // no shift overflow as i < 13
+        if chunk & 0x20 == 0 {
+            *polyline = &polyline[i + 1..];
+            return Some(result);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Test vectors from https://github.com/heremaps/flexible-polyline
+    #[test]
+    fn reference_vectors() {
+        let points = [
+            (50.1022829, 8.6982122),
+            (50.1020076, 8.6956695),
+            (50.1006313, 8.6914960),
+            (50.0987800, 8.6875156),
+        ];
+        assert_eq!(encode(5, points), "BFoz5xJ67i1B1B7PzIhaxL7Y");
+
+        let points = [10.0, 20.0, 30.0, 40.0]
+            .into_iter()
+            .zip(points)
+            .map(|(z, (lat, lon))| (lat, lon, z));
+        assert_eq!(
+            encode_3d(5, ThirdDimension::Altitude, 0, points),
+            "BlBoz5xJ67i1BU1B7PUzIhaUxL7YU"
+        );
+    }
+
+    /// Regression vectors for every third dimension kind, precisions from 0 to 15, extreme coordinates
+    /// and negative changes, with halves rounded away from zero. They are not from the HERE test suite,
+    /// only `reference_vectors` checks compatibility with it.
+    #[test]
+    fn edge_vectors() {
+        let points = [
+            (52.5199356, 13.3866272, 1.5),
+            (52.5100899, 13.2816896, -2.25),
+            (-89.9999999, -179.9999999, 8848.86),
+            (89.9999999, 179.9999999, 0.0),
+        ];
+        let vectors = [
+            (ThirdDimension::Level, 0, 0, "BQqDaEAAH9IhMmpRoLwWhpR"),
+            (ThirdDimension::Altitude, 0, 0, "BgBqDaEAAH9IhMmpRoLwWhpR"),
+            (
+                ThirdDimension::Altitude,
+                5,
+                2,
+                "BlJ05xgKuy2xCsJx9B7vUtXhg6lbxq27kBu3g2Bgo0qiBgwo1kCrpg2B",
+            ),
+            (
+                ThirdDimension::Elevation,
+                7,
+                3,
+                "B3N4_z3pfgyxq_H49CxpgG_xhgCrqHjmol-0C9_4xmzDsqn8Q8_onprD8_xuy2G39i8Q",
+            ),
+            (
+                ThirdDimension::Custom1,
+                5,
+                2,
+                "BlL05xgKuy2xCsJx9B7vUtXhg6lbxq27kBu3g2Bgo0qiBgwo1kCrpg2B",
+            ),
+            (ThirdDimension::Custom2, 0, 0, "BwDqDaEAAH9IhMmpRoLwWhpR"),
+        ];
+        for (third_dim, precision, third_dim_precision, expected) in vectors {
+            let polyline = encode_3d(precision, third_dim, third_dim_precision, points);
+            assert_eq!(polyline, expected);
+            let iter = decode(expected).unwrap();
+            assert_eq!(
+                iter.header(),
+                Header {
+                    precision,
+                    third_dim,
+                    third_dim_precision
+                }
+            );
+            assert_eq!(iter.count(), points.len());
+        }
+
+        // Precision 15 for all three dimensions, with the third one scaled down to fit into i64
+        let points = points.map(|(lat, lon, z)| (lat, lon, z / 10_000.0));
+        assert_eq!(
+            encode_3d(15, ThirdDimension::Custom2, 15, points),
+            "B__Bgg-hipzmtp9Cggw8_mok-4Xggup2s3I_v8tuhj9R__v8rwl8-F__y33v6V__wguv_q4k9H_vvwnshr5q3Kggz332ngqyBgg_40v-i63_Jgg_gnr9l0v_T__tp2zkzpyB"
+        );
+
+        let halves = [(0.5, -0.5, 2.5), (1.5, -1.5, -2.5), (-0.5, 0.5, 0.0)];
+        let polyline = encode_3d(0, ThirdDimension::Elevation, 0, halves);
+        assert_eq!(polyline, "BwBCBGCBLFGG");
+        assert_eq!(
+            decode(&polyline).unwrap().collect::<Vec<_>>(),
+            [
+                (1.0, -1.0, Some(3.0)),
+                (2.0, -2.0, Some(-3.0)),
+                (-1.0, 1.0, Some(0.0))
+            ]
+        );
+    }
+
+    #[test]
+    fn saturated_coordinates() {
+        // Coordinates that don't fit into i64 are saturated, and the changes between them wrap as in the decoder
+        let polyline = encode(15, [(1e300, 0.0), (-1e300, -1e300)]);
+        assert_eq!(
+            decode(&polyline).unwrap().collect::<Vec<_>>(),
+            [
+                (i64::MAX as f64 / 1e15, 0.0, None),
+                (i64::MIN as f64 / 1e15, i64::MIN as f64 / 1e15, None)
+            ]
+        );
+        let polyline = encode_3d(
+            0,
+            ThirdDimension::Altitude,
+            0,
+            [(0.0, 0.0, f64::MAX), (0.0, 0.0, f64::MIN)],
+        );
+        assert_eq!(
+            decode(&polyline).unwrap().nth(1),
+            Some((0.0, 0.0, Some(i64::MIN as f64)))
+        );
+    }
+
+    #[test]
+    fn header() {
+        let iter = decode("BFoz5xJ67i1B1B7PzIhaxL7Y").unwrap();
+        assert_eq!(
+            iter.header(),
+            Header {
+                precision: 5,
+                third_dim: ThirdDimension::Absent,
+                third_dim_precision: 0
+            }
+        );
+
+        for third_dim in [
+            ThirdDimension::Level,
+            ThirdDimension::Altitude,
+            ThirdDimension::Elevation,
+            ThirdDimension::Custom1,
+            ThirdDimension::Custom2,
+        ] {
+            for (precision, third_dim_precision) in [(0, 0), (6, 2), (15, 15)] {
+                let polyline = encode_3d(precision, third_dim, third_dim_precision, []);
+                assert_eq!(
+                    decode(&polyline).unwrap().header(),
+                    Header {
+                        precision,
+                        third_dim,
+                        third_dim_precision
+                    }
+                );
+            }
+        }
+
+        assert_eq!(decode("").err(), Some(HeaderError::Malformed));
+        assert_eq!(decode("B").err(), Some(HeaderError::Malformed));
+        assert_eq!(decode("B!").err(), Some(HeaderError::Malformed));
+        assert_eq!(decode("CF").err(), Some(HeaderError::UnsupportedVersion(2)));
+        // third dimension 4 is reserved
+        assert_eq!(
+            decode("BgC").err(),
+            Some(HeaderError::ReservedThirdDimension)
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let points = [
+            (52.5199356, 13.3866272, 1.5),
+            (52.5100899, 13.2816896, -2.25),
+            (-89.9999999, -179.9999999, 8848.86),
+            (89.9999999, 179.9999999, 0.0),
+        ];
+        for precision in [0, 5, 7, 10, 15] {
+            let polyline = encode_3d(precision, ThirdDimension::Elevation, 2, points);
+            let decoded: Vec<_> = decode(&polyline).unwrap().collect();
+            assert_eq!(decoded.len(), points.len());
+            assert_eq!(decode(&polyline).unwrap().count(), points.len());
+            let scale = 10.0_f64.powi(precision as i32);
+            for ((lat, lon, z), original) in decoded.into_iter().zip(points) {
+                assert!((lat - original.0).abs() <= 0.5 / scale + 1e-9);
+                assert!((lon - original.1).abs() <= 0.5 / scale + 1e-9);
+                assert_eq!(z, Some(original.2));
+            }
+        }
+
+        let points = [(52.5199356, 13.3866272), (52.5100899, 13.2816896)];
+        let polyline = encode(7, points);
+        let decoded: Vec<_> = decode(&polyline)
+            .unwrap()
+            .map(|(lat, lon, z)| {
+                assert_eq!(z, None);
+                (lat, lon)
+            })
+            .collect();
+        assert_eq!(decoded, points);
+    }
+
+    #[test]
+    fn broken_string() {
+        let mut iter = decode("BFoz5xJ67i1B1B7PzIhaxL7").unwrap(); // truncated last lon
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.by_ref().count(), 3);
+
+        let mut iter = decode("BFoz5xJ67i1B1B!PzIhaxL7Y").unwrap();
+        assert_eq!(iter.next(), Some((50.10228, 8.69821, None)));
+        assert_eq!(iter.next(), None);
+    }
+}
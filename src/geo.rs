@@ -0,0 +1,218 @@
+//! Interoperability with [`geo_types`], available with the `geo-types` feature.
+//!
+//! Geometries of `geo-types` are `(x, y)`, which is `(lon, lat)` for geographic coordinates,
+//! while the rest of the crate uses `(lat, lon)` pairs as in the polyline format itself.
+//! Functions here do the swap, so `Coord::from((lat, lon))` is never needed.
+
+use geo_types::{Coord, Line, LineString, MultiPoint};
+
+use crate::{BinaryPolylineIter, PolylineIter, decode, encode, encode_binary};
+
+/// Decodes a polyline into an iterator over [`Coord`] with `x = lon` and `y = lat`.
+///
+/// ```
+/// use geo_types::coord;
+///
+/// let mut iter = polyline_iter::decode_coords(5, "angrIk~inAgwDybH");
+/// assert_eq!(iter.next(), Some(coord! { x: 12.99958, y: 55.58513 }));
+/// assert_eq!(iter.next(), Some(coord! { x: 13.04627, y: 55.61461 }));
+/// assert_eq!(iter.next(), None);
+/// ```
+pub fn decode_coords(precision: u8, polyline: &str) -> impl Iterator<Item = Coord> + '_ {
+    decode(precision, polyline).map(to_coord)
+}
+
+/// Decodes a polyline into a [`LineString`].
+///
+/// ```
+/// use geo_types::line_string;
+///
+/// assert_eq!(
+///     polyline_iter::decode_linestring(5, "angrIk~inAgwDybH"),
+///     line_string![(x: 12.99958, y: 55.58513), (x: 13.04627, y: 55.61461)]
+/// );
+/// ```
+pub fn decode_linestring(precision: u8, polyline: &str) -> LineString {
+    decode(precision, polyline).into()
+}
+
+/// Encodes a sequence of [`Coord`] with `x = lon` and `y = lat` into a polyline.
+///
+/// Works for any geometry through `geo::CoordsIter`, e.g. `encode_coords(5, polygon.exterior_coords_iter())`.
+///
+/// ```
+/// use geo_types::coord;
+///
+/// let coords = [coord! { x: 12.99958, y: 55.58513 }, coord! { x: 13.04627, y: 55.61461 }];
+/// assert_eq!(polyline_iter::encode_coords(5, coords), "angrIk~inAgwDybH");
+/// ```
+pub fn encode_coords(precision: u8, coords: impl IntoIterator<Item = Coord>) -> String {
+    encode(precision, coords.into_iter().map(from_coord))
+}
+
+/// Encodes a [`LineString`] into a polyline.
+///
+/// ```
+/// use geo_types::line_string;
+///
+/// let line = line_string![(x: 12.99958, y: 55.58513), (x: 13.04627, y: 55.61461)];
+/// assert_eq!(polyline_iter::encode_linestring(5, &line), "angrIk~inAgwDybH");
+/// ```
+pub fn encode_linestring(precision: u8, line: &LineString) -> String {
+    line.to_polyline(precision)
+}
+
+/// Encodes a [`MultiPoint`] into a polyline, keeping the order of the points.
+///
+/// ```
+/// use geo_types::{MultiPoint, point};
+///
+/// let points = MultiPoint::new(vec![point! { x: 12.99958, y: 55.58513 }, point! { x: 13.04627, y: 55.61461 }]);
+/// assert_eq!(polyline_iter::encode_multipoint(5, &points), "angrIk~inAgwDybH");
+/// ```
+pub fn encode_multipoint(precision: u8, points: &MultiPoint) -> String {
+    points.to_polyline(precision)
+}
+
+/// Geometries that are encoded as a sequence of points, the opposite of `LineString::from(PolylineIter)`.
+///
+/// ```
+/// use geo_types::{Line, coord, line_string};
+/// use polyline_iter::ToPolyline;
+///
+/// let line = line_string![(x: 12.99958, y: 55.58513), (x: 13.04627, y: 55.61461)];
+/// assert_eq!(line.to_polyline(5), "angrIk~inAgwDybH");
+///
+/// let segment = Line::new(coord! { x: 12.99958, y: 55.58513 }, coord! { x: 13.04627, y: 55.61461 });
+/// assert_eq!(segment.to_binary_polyline(5), line.to_binary_polyline(5));
+/// ```
+pub trait ToPolyline {
+    /// Iterates over the coordinates in the order they are encoded.
+    fn polyline_coords(&self) -> impl Iterator<Item = Coord> + '_;
+
+    /// Encodes the geometry with [`encode()`](crate::encode), see [`encode_coords()`].
+    fn to_polyline(&self, precision: u8) -> String {
+        encode_coords(precision, self.polyline_coords())
+    }
+
+    /// Encodes the geometry with [`encode_binary()`](crate::encode_binary).
+    fn to_binary_polyline(&self, precision: u8) -> Vec<u8> {
+        encode_binary(precision, self.polyline_coords().map(from_coord))
+    }
+}
+
+impl ToPolyline for LineString {
+    fn polyline_coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.coords().copied()
+    }
+}
+
+impl ToPolyline for MultiPoint {
+    fn polyline_coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.iter().map(|point| point.0)
+    }
+}
+
+impl ToPolyline for Line {
+    fn polyline_coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        [self.start, self.end].into_iter()
+    }
+}
+
+/// Collects the decoded points, see [`decode_linestring()`].
+impl From<PolylineIter<'_>> for LineString {
+    fn from(iter: PolylineIter<'_>) -> Self {
+        iter.map(to_coord).collect()
+    }
+}
+
+/// Collects the decoded points with `x = lon` and `y = lat`.
+///
+/// ```
+/// use geo_types::{LineString, line_string};
+///
+/// let binary = polyline_iter::encode_binary(5, [(55.58513, 12.99958), (55.61461, 13.04627)]);
+/// assert_eq!(
+///     LineString::from(polyline_iter::decode_binary(5, &binary)),
+///     line_string![(x: 12.99958, y: 55.58513), (x: 13.04627, y: 55.61461)]
+/// );
+/// ```
+impl From<BinaryPolylineIter<'_>> for LineString {
+    fn from(iter: BinaryPolylineIter<'_>) -> Self {
+        iter.map(to_coord).collect()
+    }
+}
+
+pub(crate) fn to_coord((lat, lon): (f64, f64)) -> Coord {
+    Coord { x: lon, y: lat }
+}
+
+pub(crate) fn from_coord(coord: Coord) -> (f64, f64) {
+    (coord.y, coord.x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode_binary;
+    use geo_types::line_string;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn round_trip() {
+        // Antimeridian and poles, plus a change that isn't a multiple of the scale
+        let line = line_string![
+            (x: 12.99958, y: 55.58513),
+            (x: -180.0, y: 90.0),
+            (x: 180.0, y: -90.0),
+            (x: 0.000004, y: -0.000006),
+        ];
+        let polyline = encode_linestring(5, &line);
+        assert_eq!(polyline, encode(5, line.coords().copied().map(from_coord)));
+        assert_eq!(encode_coords(5, line.coords().copied()), polyline);
+        let points: MultiPoint = line.points().collect();
+        assert_eq!(encode_multipoint(5, &points), polyline);
+
+        let decoded = LineString::from(decode(5, &polyline));
+        assert_eq!(decoded.0[..3], line.0[..3]);
+        assert_eq!(
+            decoded.0[3],
+            Coord {
+                x: 0.0,
+                y: -0.00001
+            }
+        );
+        assert_eq!(decode_linestring(5, &polyline), decoded);
+        assert_eq!(decode_coords(5, &polyline).collect::<Vec<_>>(), decoded.0);
+
+        let binary = line.to_binary_polyline(5);
+        assert_eq!(binary, encode_binary(5, decode(5, &polyline)));
+        assert_eq!(points.to_binary_polyline(5), binary);
+        assert_eq!(LineString::from(decode_binary(5, &binary)), decoded);
+        let segment = Line::new(line.0[0], line.0[1]);
+        assert_eq!(
+            segment.to_polyline(5),
+            encode_coords(5, line.coords().take(2).copied())
+        );
+
+        // Malformed tail is dropped as in `decode()`
+        assert_eq!(
+            decode_linestring(5, "angrIk~inAgwD"),
+            line_string![(x: 12.99958, y: 55.58513)]
+        );
+        assert_eq!(decode_linestring(5, ""), LineString::new(Vec::new()));
+        assert_eq!(encode_linestring(5, &LineString::new(Vec::new())), "");
+        assert_eq!(encode_multipoint(5, &MultiPoint::new(Vec::new())), "");
+    }
+
+    #[test]
+    fn axis_order() {
+        let line = line_string![(x: -122.40636, y: 37.78289), (x: 151.20699, y: -33.86785)];
+        let polyline = encode_linestring(5, &line);
+        assert_eq!(
+            decode(5, &polyline).collect::<Vec<_>>(),
+            [(37.78289, -122.40636), (-33.86785, 151.20699)]
+        );
+        assert_eq!(decode_linestring(5, &polyline), line);
+    }
+}
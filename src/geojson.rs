@@ -0,0 +1,462 @@
+//! GeoJSON `LineString` writer and a minimal coordinates parser, without a JSON dependency.
+
+use std::fmt;
+
+use crate::decode;
+
+/// How coordinates are formatted by [`to_geojson_linestring_with()`] and the WKT writers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FloatFormat {
+    /// The shortest representation that parses back into the same `f64`, e.g. `13.1` or `55.58513`.
+    #[default]
+    Shortest,
+    /// Fixed number of decimal places, e.g. `13.10000` and `55.58513` for 5.
+    Fixed(usize),
+}
+
+impl FloatFormat {
+    pub(crate) fn write(self, value: f64, out: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            FloatFormat::Shortest => write!(out, "{value}"),
+            FloatFormat::Fixed(decimals) => write!(out, "{value:.decimals$}"),
+        }
+    }
+}
+
+/// Error returned by [`parse_geojson()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeoJsonError {
+    /// Malformed JSON or a position with less than 2 numbers, at the given byte offset.
+    Syntax { offset: usize },
+    /// The geometry type is missing or is neither `LineString` nor `MultiPoint`.
+    UnsupportedType,
+    /// The geometry has no `coordinates` member.
+    MissingCoordinates,
+}
+
+impl fmt::Display for GeoJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeoJsonError::Syntax { offset } => write!(f, "invalid GeoJSON at offset {offset}"),
+            GeoJsonError::UnsupportedType => {
+                write!(f, "GeoJSON geometry is neither LineString nor MultiPoint")
+            }
+            GeoJsonError::MissingCoordinates => write!(f, "GeoJSON geometry has no coordinates"),
+        }
+    }
+}
+
+impl std::error::Error for GeoJsonError {}
+
+/// Writes a polyline as a GeoJSON `LineString` geometry, formatting coordinates with [`FloatFormat::Shortest`].
+/// Like the decoder, the writer stops at the first malformed point and keeps the ones before it.
+///
+/// ```
+/// let mut json = String::new();
+/// polyline_iter::to_geojson_linestring(5, "angrIk~inAgwDybH", &mut json).unwrap();
+/// assert_eq!(json, r#"{"type":"LineString","coordinates":[[12.99958,55.58513],[13.04627,55.61461]]}"#);
+/// ```
+pub fn to_geojson_linestring(
+    precision: u8,
+    polyline: &str,
+    out: &mut impl fmt::Write,
+) -> fmt::Result {
+    to_geojson_linestring_with(precision, FloatFormat::Shortest, polyline, out)
+}
+
+/// Like [`to_geojson_linestring()`], but with the explicit coordinates format.
+///
+/// ```
+/// use polyline_iter::FloatFormat;
+///
+/// let mut json = String::new();
+/// polyline_iter::to_geojson_linestring_with(5, FloatFormat::Fixed(5), "_ibE_ibE", &mut json).unwrap();
+/// assert_eq!(json, r#"{"type":"LineString","coordinates":[[1.00000,1.00000]]}"#);
+/// ```
+pub fn to_geojson_linestring_with(
+    precision: u8,
+    format: FloatFormat,
+    polyline: &str,
+    out: &mut impl fmt::Write,
+) -> fmt::Result {
+    out.write_str(r#"{"type":"LineString","coordinates":["#)?;
+    for (i, (lat, lon)) in decode(precision, polyline).enumerate() {
+        out.write_str(if i == 0 { "[" } else { ",[" })?;
+        // GeoJSON positions are [lon, lat]
+        format.write(lon, out)?;
+        out.write_char(',')?;
+        format.write(lat, out)?;
+        out.write_char(']')?;
+    }
+    out.write_str("]}")
+}
+
+/// Parses the points of a GeoJSON `LineString` or `MultiPoint` geometry, or a bare coordinates array,
+/// into `(lat, lon)` pairs, ready for [`encode()`](crate::encode). Members other than `type` and
+/// `coordinates` are skipped, as well as altitudes and other extra numbers of the positions.
+///
+/// ```
+/// let json = r#"{"type": "MultiPoint", "coordinates": [[12.99958, 55.58513, 10.5], [13.04627, 55.61461, 12.0]]}"#;
+/// let points = polyline_iter::parse_geojson(json).unwrap();
+/// assert_eq!(points, [(55.58513, 12.99958), (55.61461, 13.04627)]);
+/// assert_eq!(polyline_iter::encode(5, points), "angrIk~inAgwDybH");
+///
+/// assert_eq!(polyline_iter::parse_geojson("[[13.1, 55.6]]").unwrap(), [(55.6, 13.1)]);
+/// ```
+pub fn parse_geojson(json: &str) -> Result<Vec<(f64, f64)>, GeoJsonError> {
+    let mut parser = Parser {
+        json: json.as_bytes(),
+        offset: 0,
+    };
+    parser.skip_whitespace();
+    let points = if parser.peek() == Some(b'[') {
+        parser.coordinates()?
+    } else {
+        parser.geometry()?
+    };
+    parser.skip_whitespace();
+    if parser.offset != parser.json.len() {
+        return Err(parser.error());
+    }
+    Ok(points)
+}
+
+/// Recursive descent parser over the bytes of a JSON document.
+struct Parser<'a> {
+    json: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self) -> GeoJsonError {
+        GeoJsonError::Syntax {
+            offset: self.offset,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.json.get(self.offset).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.offset += 1;
+        }
+    }
+
+    /// Skips whitespace and consumes the expected byte.
+    fn expect(&mut self, byte: u8) -> Result<(), GeoJsonError> {
+        self.skip_whitespace();
+        if self.peek() != Some(byte) {
+            return Err(self.error());
+        }
+        self.offset += 1;
+        Ok(())
+    }
+
+    /// Skips whitespace and consumes the byte if it is the next one.
+    fn consume(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        let found = self.peek() == Some(byte);
+        if found {
+            self.offset += 1;
+        }
+        found
+    }
+
+    /// Parses an object with `type` and `coordinates` members.
+    fn geometry(&mut self) -> Result<Vec<(f64, f64)>, GeoJsonError> {
+        self.expect(b'{')?;
+        let mut supported_type = false;
+        // Coordinates may come before the type, so they are parsed only after the whole object
+        let mut coordinates = None;
+        if !self.consume(b'}') {
+            loop {
+                let key = self.string()?;
+                self.expect(b':')?;
+                match key {
+                    b"type" => {
+                        supported_type = matches!(self.string()?, b"LineString" | b"MultiPoint")
+                    }
+                    b"coordinates" => {
+                        self.skip_whitespace();
+                        coordinates = Some(self.offset);
+                        self.skip_value()?;
+                    }
+                    _ => self.skip_value()?,
+                }
+                if !self.consume(b',') {
+                    break;
+                }
+            }
+            self.expect(b'}')?;
+        }
+        if !supported_type {
+            return Err(GeoJsonError::UnsupportedType);
+        }
+        let end = self.offset;
+        self.offset = coordinates.ok_or(GeoJsonError::MissingCoordinates)?;
+        let points = self.coordinates()?;
+        self.offset = end;
+        Ok(points)
+    }
+
+    /// Parses an array of positions, each of which is an array of at least 2 numbers.
+    fn coordinates(&mut self) -> Result<Vec<(f64, f64)>, GeoJsonError> {
+        self.expect(b'[')?;
+        let mut points = Vec::new();
+        if self.consume(b']') {
+            return Ok(points);
+        }
+        loop {
+            self.expect(b'[')?;
+            let lon = self.number()?;
+            self.expect(b',')?;
+            let lat = self.number()?;
+            while self.consume(b',') {
+                self.number()?;
+            }
+            self.expect(b']')?;
+            points.push((lat, lon));
+            if !self.consume(b',') {
+                break;
+            }
+        }
+        self.expect(b']')?;
+        Ok(points)
+    }
+
+    /// Returns the raw contents of a string, without unescaping.
+    fn string(&mut self) -> Result<&'a [u8], GeoJsonError> {
+        self.expect(b'"')?;
+        let start = self.offset;
+        loop {
+            match self.peek() {
+                Some(b'"') => break,
+                Some(b'\\') => self.offset += 2,
+                Some(_) => self.offset += 1,
+                None => return Err(self.error()),
+            }
+        }
+        let string = &self.json[start..self.offset];
+        self.offset += 1;
+        Ok(string)
+    }
+
+    fn number(&mut self) -> Result<f64, GeoJsonError> {
+        self.skip_whitespace();
+        let start = self.offset;
+        while let Some(b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E') = self.peek() {
+            self.offset += 1;
+        }
+        // `f64::from_str()` also accepts a few non-JSON forms, like `+1` or `1.`, which is fine for a minimal parser
+        std::str::from_utf8(&self.json[start..self.offset])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .ok_or(GeoJsonError::Syntax { offset: start })
+    }
+
+    /// Skips any JSON value. Nesting is tracked on the heap, so deeply nested input can't overflow the stack.
+    fn skip_value(&mut self) -> Result<(), GeoJsonError> {
+        // Closing brackets of the arrays and objects the current value is nested in
+        let mut closers = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b'"') => {
+                    self.string()?;
+                }
+                Some(open @ (b'[' | b'{')) => {
+                    self.offset += 1;
+                    let close = if open == b'[' { b']' } else { b'}' };
+                    if !self.consume(close) {
+                        closers.push(close);
+                        if close == b'}' {
+                            self.string()?;
+                            self.expect(b':')?;
+                        }
+                        continue;
+                    }
+                }
+                _ => {
+                    let start = self.offset;
+                    while let Some(b'a'..=b'z' | b'0'..=b'9' | b'-' | b'+' | b'.' | b'E') =
+                        self.peek()
+                    {
+                        self.offset += 1;
+                    }
+                    let literal = &self.json[start..self.offset];
+                    let valid = matches!(literal, b"true" | b"false" | b"null")
+                        || std::str::from_utf8(literal)
+                            .is_ok_and(|number| number.parse::<f64>().is_ok());
+                    if !valid {
+                        return Err(GeoJsonError::Syntax { offset: start });
+                    }
+                }
+            }
+            // The value is complete, so either the next member follows or the enclosing containers end
+            loop {
+                let Some(&close) = closers.last() else {
+                    return Ok(());
+                };
+                if self.consume(b',') {
+                    if close == b'}' {
+                        self.string()?;
+                        self.expect(b':')?;
+                    }
+                    break;
+                }
+                self.expect(close)?;
+                closers.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode;
+    use crate::test_data::{EDGE_LINES6, edge_polylines6};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn round_trip() {
+        for (line, polyline) in EDGE_LINES6.iter().zip(edge_polylines6()) {
+            for format in [FloatFormat::Shortest, FloatFormat::Fixed(6)] {
+                let mut json = String::new();
+                to_geojson_linestring_with(6, format, &polyline, &mut json).unwrap();
+                // JSON allows exponents, but `Shortest` never needs them for degrees
+                assert!(!json.contains("e-"), "{json}");
+                assert_eq!(parse_geojson(&json).as_deref(), Ok(*line));
+            }
+        }
+
+        let mut json = String::new();
+        to_geojson_linestring(5, "", &mut json).unwrap();
+        assert_eq!(json, r#"{"type":"LineString","coordinates":[]}"#);
+        assert_eq!(parse_geojson(&json), Ok(vec![]));
+    }
+
+    #[test]
+    fn writer() {
+        let polyline = encode(5, [(-33.86785, 151.20699), (0.0, -0.00001), (90.0, -180.0)]);
+        let mut json = String::new();
+        to_geojson_linestring(5, &polyline, &mut json).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"LineString","coordinates":[[151.20699,-33.86785],[-0.00001,0],[-180,90]]}"#
+        );
+
+        let mut json = String::new();
+        to_geojson_linestring_with(5, FloatFormat::Fixed(2), &polyline, &mut json).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"LineString","coordinates":[[151.21,-33.87],[-0.00,0.00],[-180.00,90.00]]}"#
+        );
+
+        // Malformed tail is dropped
+        let mut json = String::new();
+        to_geojson_linestring(5, "angrIk~inAgwD", &mut json).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"LineString","coordinates":[[12.99958,55.58513]]}"#
+        );
+    }
+
+    #[test]
+    fn parser() {
+        let expected = vec![(55.58513, 12.99958), (55.61461, 13.04627)];
+        let json = r#"
+            {
+                "bbox": [12.99958, 55.58513, 13.04627, 55.61461],
+                "coordinates": [ [ 12.99958 , 55.58513 ] , [1.304627E1, 55.61461, 0, 1700000000] ],
+                "properties": {"name": "A \"quoted\" name", "nested": [{}, [], null, true, -1e-3]},
+                "type": "LineString"
+            }
+        "#;
+        assert_eq!(parse_geojson(json), Ok(expected.clone()));
+        assert_eq!(
+            parse_geojson(
+                r#"{"type":"MultiPoint","coordinates":[[12.99958,55.58513],[13.04627,55.61461]]}"#
+            ),
+            Ok(expected)
+        );
+        assert_eq!(parse_geojson(" [ ] "), Ok(vec![]));
+    }
+
+    #[test]
+    fn deeply_nested_properties() {
+        let depth = 100_000;
+        let arrays = format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        let objects = format!("{}null{}", r#"{"a":"#.repeat(depth), "}".repeat(depth));
+        for nested in [&arrays, &objects] {
+            let json =
+                format!(r#"{{"type":"LineString","coordinates":[[1,2]],"properties":{nested}}}"#);
+            assert_eq!(parse_geojson(&json), Ok(vec![(2.0, 1.0)]));
+
+            // Unbalanced nesting is reported where the missing bracket is expected
+            let json = format!(
+                r#"{{"type":"LineString","coordinates":[],"p":{}}}"#,
+                &nested[1..]
+            );
+            assert!(matches!(
+                parse_geojson(&json),
+                Err(GeoJsonError::Syntax { .. })
+            ));
+        }
+        let json = format!(
+            r#"{{"type":"LineString","coordinates":[],"p":{}"#,
+            "[".repeat(depth)
+        );
+        assert_eq!(
+            parse_geojson(&json),
+            Err(GeoJsonError::Syntax { offset: json.len() })
+        );
+    }
+
+    #[test]
+    fn parser_errors() {
+        assert_eq!(
+            parse_geojson(r#"{"type":"Point","coordinates":[1,2]}"#),
+            Err(GeoJsonError::UnsupportedType)
+        );
+        assert_eq!(
+            parse_geojson(r#"{"coordinates":[]}"#),
+            Err(GeoJsonError::UnsupportedType)
+        );
+        assert_eq!(
+            parse_geojson(r#"{"type":"LineString"}"#),
+            Err(GeoJsonError::MissingCoordinates)
+        );
+        assert_eq!(
+            parse_geojson("[[1]]"),
+            Err(GeoJsonError::Syntax { offset: 3 })
+        );
+        assert_eq!(
+            parse_geojson("[[1,2],]"),
+            Err(GeoJsonError::Syntax { offset: 7 })
+        );
+        assert_eq!(
+            parse_geojson("[[1,x]]"),
+            Err(GeoJsonError::Syntax { offset: 4 })
+        );
+        assert_eq!(
+            parse_geojson("[[1,2]] []"),
+            Err(GeoJsonError::Syntax { offset: 8 })
+        );
+        assert_eq!(
+            parse_geojson(r#"{"type":"LineString","coordinates":[],"x":nope}"#),
+            Err(GeoJsonError::Syntax { offset: 42 })
+        );
+        assert_eq!(
+            parse_geojson(r#"{"type":"LineString"#),
+            Err(GeoJsonError::Syntax { offset: 19 })
+        );
+        assert_eq!(parse_geojson(""), Err(GeoJsonError::Syntax { offset: 0 }));
+        assert_eq!(
+            GeoJsonError::Syntax { offset: 3 }.to_string(),
+            "invalid GeoJSON at offset 3"
+        );
+    }
+}
@@ -0,0 +1,729 @@
+//! GPX 1.1 writer and reader for tracks and routes, without an XML dependency.
+//!
+//! The reader is a small tolerant scanner rather than a validating XML parser: namespace prefixes are ignored,
+//! as well as waypoints, metadata, extensions and any unknown elements. Times are unix timestamps in seconds,
+//! so they fit [`encode_nd()`](crate::encode_nd) with e.g. `Scale::from_factor(1.0)`.
+
+use std::fmt;
+
+use crate::{Scale, decode, decode_3d, decode_nd, encode, encode_3d, encode_nd};
+
+/// How polylines are represented in GPX.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GpxKind {
+    /// A `<trkseg>` of `<trkpt>`, all segments are written into a single `<trk>`.
+    #[default]
+    Track,
+    /// A `<rte>` of `<rtept>`.
+    Route,
+}
+
+/// Error returned by the GPX readers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpxError {
+    /// Unterminated tag, comment or segment at the given byte offset.
+    Syntax { offset: usize },
+    /// Missing or invalid `lat`, `lon`, `<ele>` or `<time>` of a point at the given byte offset.
+    InvalidPoint { offset: usize },
+}
+
+impl fmt::Display for GpxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpxError::Syntax { offset } => write!(f, "invalid GPX at offset {offset}"),
+            GpxError::InvalidPoint { offset } => write!(f, "invalid GPX point at offset {offset}"),
+        }
+    }
+}
+
+impl std::error::Error for GpxError {}
+
+/// Writes polylines as a GPX document, one segment or route per polyline.
+/// A malformed polyline ends its segment or route early instead of failing the whole document.
+///
+/// ```
+/// use polyline_iter::GpxKind;
+///
+/// let mut gpx = String::new();
+/// polyline_iter::to_gpx(5, GpxKind::Route, ["angrIk~inAgwDybH"], &mut gpx).unwrap();
+/// assert!(gpx.contains(r#"<rtept lat="55.58513" lon="12.99958"/>"#));
+/// assert_eq!(polyline_iter::parse_gpx(5, &gpx), Ok(vec![(GpxKind::Route, "angrIk~inAgwDybH".to_string())]));
+/// ```
+pub fn to_gpx<'a>(
+    precision: u8,
+    kind: GpxKind,
+    polylines: impl IntoIterator<Item = &'a str>,
+    out: &mut impl fmt::Write,
+) -> fmt::Result {
+    write_gpx(kind, polylines, out, |polyline, out| {
+        for (lat, lon) in decode(precision, polyline) {
+            write_point(kind, lat, lon, None, None, out)?;
+        }
+        Ok(())
+    })
+}
+
+/// Like [`to_gpx()`], but for polylines with elevation, see [`decode_3d()`], which is written as `<ele>`.
+///
+/// ```
+/// use polyline_iter::GpxKind;
+///
+/// let mut gpx = String::new();
+/// polyline_iter::to_gpx_3d(5, 2, GpxKind::Track, ["angrIk~inAklB}{AsAcB"], &mut gpx).unwrap();
+/// assert!(gpx.contains(r#"<trkpt lat="55.58513" lon="12.99958"><ele>17.5</ele></trkpt>"#));
+/// ```
+pub fn to_gpx_3d<'a>(
+    precision: u8,
+    elevation_precision: u8,
+    kind: GpxKind,
+    polylines: impl IntoIterator<Item = &'a str>,
+    out: &mut impl fmt::Write,
+) -> fmt::Result {
+    write_gpx(kind, polylines, out, |polyline, out| {
+        for (lat, lon, elevation) in decode_3d(precision, elevation_precision, polyline) {
+            write_point(kind, lat, lon, Some(elevation), None, out)?;
+        }
+        Ok(())
+    })
+}
+
+/// Like [`to_gpx()`], but for `[lat, lon, elevation, time]` polylines, see [`decode_nd()`], where time is
+/// a unix timestamp in seconds written as `<time>` in UTC with up to millisecond precision.
+///
+/// ```
+/// use polyline_iter::{GpxKind, Scale};
+///
+/// let scales = [Scale::from_precision(5), Scale::from_precision(5), Scale::from_precision(1), Scale::from_factor(1.0)];
+/// let polyline = polyline_iter::encode_nd(scales, [[55.58513, 12.99958, 17.5, 1_700_000_000.0]]);
+/// let mut gpx = String::new();
+/// polyline_iter::to_gpx_nd(scales, GpxKind::Track, [polyline.as_str()], &mut gpx).unwrap();
+/// assert!(gpx.contains("<ele>17.5</ele><time>2023-11-14T22:13:20Z</time>"));
+/// ```
+pub fn to_gpx_nd<'a>(
+    scales: [Scale; 4],
+    kind: GpxKind,
+    polylines: impl IntoIterator<Item = &'a str>,
+    out: &mut impl fmt::Write,
+) -> fmt::Result {
+    write_gpx(kind, polylines, out, |polyline, out| {
+        for [lat, lon, elevation, time] in decode_nd(scales, polyline) {
+            write_point(kind, lat, lon, Some(elevation), Some(time), out)?;
+        }
+        Ok(())
+    })
+}
+
+fn write_gpx<'a, W: fmt::Write>(
+    kind: GpxKind,
+    polylines: impl IntoIterator<Item = &'a str>,
+    out: &mut W,
+    mut write_points: impl FnMut(&'a str, &mut W) -> fmt::Result,
+) -> fmt::Result {
+    out.write_str(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<gpx version=\"1.1\" creator=\"polyline-iter\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n",
+    ))?;
+    match kind {
+        GpxKind::Track => {
+            out.write_str("  <trk>\n")?;
+            for polyline in polylines {
+                out.write_str("    <trkseg>\n")?;
+                write_points(polyline, out)?;
+                out.write_str("    </trkseg>\n")?;
+            }
+            out.write_str("  </trk>\n")?;
+        }
+        GpxKind::Route => {
+            for polyline in polylines {
+                out.write_str("  <rte>\n")?;
+                write_points(polyline, out)?;
+                out.write_str("  </rte>\n")?;
+            }
+        }
+    }
+    out.write_str("</gpx>\n")
+}
+
+fn write_point(
+    kind: GpxKind,
+    lat: f64,
+    lon: f64,
+    elevation: Option<f64>,
+    time: Option<f64>,
+    out: &mut impl fmt::Write,
+) -> fmt::Result {
+    let (indent, tag) = match kind {
+        GpxKind::Track => ("      ", "trkpt"),
+        GpxKind::Route => ("    ", "rtept"),
+    };
+    write!(out, "{indent}<{tag} lat=\"{lat}\" lon=\"{lon}\"")?;
+    if elevation.is_none() && time.is_none() {
+        return out.write_str("/>\n");
+    }
+    out.write_char('>')?;
+    if let Some(elevation) = elevation {
+        write!(out, "<ele>{elevation}</ele>")?;
+    }
+    if let Some(time) = time {
+        out.write_str("<time>")?;
+        write_time(time, out)?;
+        out.write_str("</time>")?;
+    }
+    writeln!(out, "</{tag}>")
+}
+
+/// Reads track segments and routes of a GPX document in the document order, encoding each into a polyline.
+///
+/// ```
+/// use polyline_iter::GpxKind;
+///
+/// let gpx = r#"<gpx:gpx xmlns:gpx="http://www.topografix.com/GPX/1/1"><gpx:trk><gpx:trkseg>
+///     <gpx:trkpt lon="12.99958" lat="55.58513"><gpx:ele>17.5</gpx:ele></gpx:trkpt>
+///     <gpx:trkpt lon="13.04627" lat="55.61461"/>
+/// </gpx:trkseg></gpx:trk></gpx:gpx>"#;
+/// assert_eq!(polyline_iter::parse_gpx(5, gpx), Ok(vec![(GpxKind::Track, "angrIk~inAgwDybH".to_string())]));
+/// ```
+pub fn parse_gpx(precision: u8, gpx: &str) -> Result<Vec<(GpxKind, String)>, GpxError> {
+    let segments = segments(gpx)?.into_iter().map(|(kind, points)| {
+        let polyline = encode(precision, points.iter().map(|point| (point.lat, point.lon)));
+        (kind, polyline)
+    });
+    Ok(segments.collect())
+}
+
+/// Like [`parse_gpx()`], but also encodes `<ele>` as in [`encode_3d()`]. Points without `<ele>` get zero elevation.
+///
+/// ```
+/// use polyline_iter::GpxKind;
+///
+/// let gpx = r#"<gpx><rte>
+///     <rtept lat="55.58513" lon="12.99958"><ele>17.5</ele></rtept>
+///     <rtept lat="55.6" lon="13.0"><ele>18</ele></rtept>
+/// </rte></gpx>"#;
+/// assert_eq!(polyline_iter::parse_gpx_3d(5, 2, gpx), Ok(vec![(GpxKind::Route, "angrIk~inAklB}{AsAcB".to_string())]));
+/// ```
+pub fn parse_gpx_3d(
+    precision: u8,
+    elevation_precision: u8,
+    gpx: &str,
+) -> Result<Vec<(GpxKind, String)>, GpxError> {
+    segments(gpx)?
+        .into_iter()
+        .map(|(kind, points)| {
+            let points = points
+                .iter()
+                .map(|point| Ok((point.lat, point.lon, point.elevation()?)))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok((kind, encode_3d(precision, elevation_precision, points)))
+        })
+        .collect()
+}
+
+/// Like [`parse_gpx()`], but encodes `[lat, lon, elevation, time]` points as in [`encode_nd()`],
+/// where time is a unix timestamp in seconds. Points without `<ele>` or `<time>` get zero instead.
+///
+/// ```
+/// use polyline_iter::Scale;
+///
+/// let gpx = r#"<gpx><trk><trkseg>
+///     <trkpt lat="55.58513" lon="12.99958"><ele>17.5</ele><time>2023-11-14T22:13:20Z</time></trkpt>
+///     <trkpt lat="55.58600" lon="12.99900"><ele>18</ele><time>2023-11-15T00:13:25.5+02:00</time></trkpt>
+/// </trkseg></trk></gpx>"#;
+/// let scales = [Scale::from_precision(5), Scale::from_precision(5), Scale::from_precision(1), Scale::from_factor(10.0)];
+/// let (_, polyline) = &polyline_iter::parse_gpx_nd(scales, gpx).unwrap()[0];
+/// assert_eq!(
+///     polyline_iter::decode_nd(scales, polyline).collect::<Vec<_>>(),
+///     [[55.58513, 12.99958, 17.5, 1_700_000_000.0], [55.586, 12.999, 18.0, 1_700_000_005.5]]
+/// );
+/// ```
+pub fn parse_gpx_nd(scales: [Scale; 4], gpx: &str) -> Result<Vec<(GpxKind, String)>, GpxError> {
+    segments(gpx)?
+        .into_iter()
+        .map(|(kind, points)| {
+            let points = points
+                .iter()
+                .map(|point| Ok([point.lat, point.lon, point.elevation()?, point.time()?]))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok((kind, encode_nd(scales, points)))
+        })
+        .collect()
+}
+
+/// Point with `<ele>` and `<time>` kept as text, so they are parsed only when needed.
+struct RawPoint<'a> {
+    lat: f64,
+    lon: f64,
+    elevation: Option<Text<'a>>,
+    time: Option<Text<'a>>,
+}
+
+#[derive(Clone, Copy)]
+struct Text<'a> {
+    offset: usize,
+    text: &'a str,
+}
+
+impl RawPoint<'_> {
+    fn elevation(&self) -> Result<f64, GpxError> {
+        self.elevation.map_or(Ok(0.0), |value| {
+            let elevation = value.text.parse::<f64>().ok().filter(|e| e.is_finite());
+            elevation.ok_or(GpxError::InvalidPoint {
+                offset: value.offset,
+            })
+        })
+    }
+
+    fn time(&self) -> Result<f64, GpxError> {
+        self.time.map_or(Ok(0.0), |value| {
+            parse_time(value.text).ok_or(GpxError::InvalidPoint {
+                offset: value.offset,
+            })
+        })
+    }
+}
+
+/// Scans the document for `<trkseg>` and `<rte>` with their points.
+fn segments(gpx: &str) -> Result<Vec<(GpxKind, Vec<RawPoint<'_>>)>, GpxError> {
+    let mut segments = Vec::new();
+    // Each open element is tracked only by its depth, so mismatched end tag names are tolerated
+    let mut depth = 0;
+    let mut segment: Option<(usize, GpxKind, Vec<RawPoint>)> = None;
+    let mut point: Option<(usize, RawPoint)> = None;
+    // Depth of `<ele>` or `<time>`, whether it is `<time>`, and the offset of its text
+    let mut field: Option<(usize, bool, usize)> = None;
+
+    let mut offset = 0;
+    while let Some(start) = gpx[offset..].find('<').map(|i| offset + i) {
+        let rest = &gpx[start..];
+        let syntax_error = GpxError::Syntax { offset: start };
+        // Declarations, comments and CDATA are skipped
+        for (prefix, suffix) in [
+            ("<?", "?>"),
+            ("<!--", "-->"),
+            ("<![CDATA[", "]]>"),
+            ("<!", ">"),
+        ] {
+            if let Some(skipped) = rest.strip_prefix(prefix) {
+                let end = skipped.find(suffix).ok_or(syntax_error)?;
+                offset = start + prefix.len() + end + suffix.len();
+                break;
+            }
+        }
+        if offset > start {
+            continue;
+        }
+        let len = tag_len(rest).ok_or(syntax_error)?;
+        offset = start + len;
+
+        if rest.starts_with("</") {
+            depth = usize::saturating_sub(depth, 1);
+            if let Some((field_depth, is_time, text_offset)) = field {
+                if field_depth == depth {
+                    let text = Some(Text {
+                        offset: text_offset,
+                        text: gpx[text_offset..start].trim(),
+                    });
+                    let (_, point) = point.as_mut().expect("fields are inside points");
+                    if is_time {
+                        point.time = text;
+                    } else {
+                        point.elevation = text;
+                    }
+                    field = None;
+                }
+            } else if point
+                .as_ref()
+                .is_some_and(|(point_depth, _)| *point_depth == depth)
+            {
+                let (_, point) = point.take().expect("point is checked");
+                let (_, _, points) = segment.as_mut().expect("points are inside segments");
+                points.push(point);
+            } else if segment
+                .as_ref()
+                .is_some_and(|(segment_depth, _, _)| *segment_depth == depth)
+            {
+                let (_, kind, points) = segment.take().expect("segment is checked");
+                segments.push((kind, points));
+            }
+            continue;
+        }
+
+        let content = &rest[1..len - 1];
+        let self_closing = content.ends_with('/');
+        let content = content.strip_suffix('/').unwrap_or(content);
+        let name_len = content
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(content.len());
+        let (name, attributes) = content.split_at(name_len);
+        let local_name = name.rsplit(':').next().unwrap_or(name);
+        let element_depth = depth;
+        if !self_closing {
+            depth += 1;
+        }
+
+        match (local_name, &mut segment, &mut point) {
+            ("trkseg" | "rte", None, _) => {
+                let kind = if local_name == "rte" {
+                    GpxKind::Route
+                } else {
+                    GpxKind::Track
+                };
+                if self_closing {
+                    segments.push((kind, Vec::new()));
+                } else {
+                    segment = Some((element_depth, kind, Vec::new()));
+                }
+            }
+            ("trkpt" | "rtept", Some((segment_depth, kind, points)), None)
+                if element_depth == *segment_depth + 1
+                    && (local_name == "rtept") == (*kind == GpxKind::Route) =>
+            {
+                let invalid_point = GpxError::InvalidPoint { offset: start };
+                let lat = attribute(attributes, "lat")
+                    .and_then(|lat| lat.parse::<f64>().ok())
+                    .filter(|lat| (-90.0..=90.0).contains(lat))
+                    .ok_or(invalid_point)?;
+                let lon = attribute(attributes, "lon")
+                    .and_then(|lon| lon.parse::<f64>().ok())
+                    .filter(|lon| (-180.0..=180.0).contains(lon))
+                    .ok_or(invalid_point)?;
+                let new_point = RawPoint {
+                    lat,
+                    lon,
+                    elevation: None,
+                    time: None,
+                };
+                if self_closing {
+                    points.push(new_point);
+                } else {
+                    point = Some((element_depth, new_point));
+                }
+            }
+            ("ele" | "time", _, Some((point_depth, _)))
+                if element_depth == *point_depth + 1 && !self_closing =>
+            {
+                field = Some((element_depth, local_name == "time", offset));
+            }
+            _ => {}
+        }
+    }
+
+    if segment.is_some() {
+        return Err(GpxError::Syntax { offset: gpx.len() });
+    }
+    Ok(segments)
+}
+
+/// Returns the length of the tag at the start of `xml` including `<` and `>`, skipping `>` in quoted values.
+fn tag_len(xml: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, byte) in xml.bytes().enumerate() {
+        match (byte, quote) {
+            (b'"' | b'\'', None) => quote = Some(byte),
+            (_, Some(q)) if byte == q => quote = None,
+            (b'>', None) => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Finds the value of an attribute by its local name, e.g. both `lat="1"` and `gpx:lat='1'` match `lat`.
+fn attribute<'a>(mut attributes: &'a str, local_name: &str) -> Option<&'a str> {
+    loop {
+        let (name, rest) = attributes.split_once('=')?;
+        let rest = rest.trim_start();
+        let quote = rest.chars().next().filter(|&c| c == '"' || c == '\'')?;
+        let (value, rest) = rest[1..].split_once(quote)?;
+        let name = name.trim();
+        if name.rsplit(':').next() == Some(local_name) {
+            return Some(value.trim());
+        }
+        attributes = rest;
+    }
+}
+
+/// Writes a unix timestamp in seconds as `2023-11-14T22:13:20.5Z`, rounding to milliseconds.
+fn write_time(time: f64, out: &mut impl fmt::Write) -> fmt::Result {
+    let millis = (time * 1000.0).round() as i64;
+    let seconds = millis.div_euclid(1000);
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86400));
+    let seconds = seconds.rem_euclid(86400);
+    write!(
+        out,
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )?;
+    let fraction = millis.rem_euclid(1000);
+    if fraction != 0 {
+        let fraction = format!("{fraction:03}");
+        write!(out, ".{}", fraction.trim_end_matches('0'))?;
+    }
+    out.write_char('Z')
+}
+
+/// Parses an RFC 3339 time like `2023-11-14T22:13:20Z` or `2023-11-15T00:13:20.5+02:00` into a unix timestamp
+/// in seconds. Time without a zone is treated as UTC, as GPX requires UTC anyway.
+fn parse_time(time: &str) -> Option<f64> {
+    let number = |range: std::ops::Range<usize>| {
+        let digits = time.get(range)?;
+        digits
+            .bytes()
+            .all(|byte| byte.is_ascii_digit())
+            .then(|| digits.parse::<i64>().ok())?
+    };
+    let bytes = time.as_bytes();
+    let separators = [(4, b'-'), (7, b'-'), (13, b':'), (16, b':')];
+    if bytes.len() < 19
+        || !separators.iter().all(|&(i, c)| bytes[i] == c)
+        || !matches!(bytes[10], b'T' | b't' | b' ')
+    {
+        return None;
+    }
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return None;
+    }
+
+    let mut rest = &time[19..];
+    let mut fraction = 0.0;
+    if rest.starts_with('.') {
+        let len = rest[1..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - 1);
+        if len == 0 {
+            return None;
+        }
+        fraction = format!("0{}", &rest[..len + 1]).parse().ok()?;
+        rest = &rest[len + 1..];
+    }
+    let zone_offset = match rest.as_bytes() {
+        [] | [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2] => {
+            let digits = [*h1, *h2, *m1, *m2];
+            if !digits.iter().all(u8::is_ascii_digit) {
+                return None;
+            }
+            let [h1, h2, m1, m2] = digits.map(|digit| (digit - b'0') as i64);
+            let zone_offset = (h1 * 10 + h2) * 3600 + (m1 * 10 + m2) * 60;
+            if *sign == b'-' {
+                -zone_offset
+            } else {
+                zone_offset
+            }
+        }
+        _ => return None,
+    };
+    let seconds = days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second
+        - zone_offset;
+    Some(seconds as f64 + fraction)
+}
+
+/// Days since 1970-01-01 of the proleptic Gregorian date, see <https://howardhinnant.github.io/date_algorithms.html>.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Inverse of [`days_from_civil()`].
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::edge_polylines6;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn round_trip() {
+        // The empty line is written as an empty segment or route and read back in its place
+        let polylines = edge_polylines6();
+        for kind in [GpxKind::Track, GpxKind::Route] {
+            let mut gpx = String::new();
+            to_gpx(6, kind, polylines.iter().map(String::as_str), &mut gpx).unwrap();
+            let expected: Vec<_> = polylines
+                .iter()
+                .map(|polyline| (kind, polyline.clone()))
+                .collect();
+            assert_eq!(parse_gpx(6, &gpx), Ok(expected));
+        }
+
+        let scales = [
+            Scale::from_precision(6),
+            Scale::from_precision(6),
+            Scale::from_precision(1),
+            Scale::from_factor(1000.0),
+        ];
+        let points = [
+            [55.585137, 12.999583, 12.5, 1_700_000_000.123],
+            [55.644854, 13.112187, 13.0, 1_700_000_001.5],
+            [55.678161, 13.182229, -2.1, -1.0],
+        ];
+        let polyline = encode_nd(scales, points);
+        let mut gpx = String::new();
+        to_gpx_nd(scales, GpxKind::Track, [polyline.as_str()], &mut gpx).unwrap();
+        assert_eq!(
+            gpx,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="polyline-iter" xmlns="http://www.topografix.com/GPX/1/1">
+  <trk>
+    <trkseg>
+      <trkpt lat="55.585137" lon="12.999583"><ele>12.5</ele><time>2023-11-14T22:13:20.123Z</time></trkpt>
+      <trkpt lat="55.644854" lon="13.112187"><ele>13</ele><time>2023-11-14T22:13:21.5Z</time></trkpt>
+      <trkpt lat="55.678161" lon="13.182229"><ele>-2.1</ele><time>1969-12-31T23:59:59Z</time></trkpt>
+    </trkseg>
+  </trk>
+</gpx>
+"#
+        );
+        assert_eq!(
+            parse_gpx_nd(scales, &gpx),
+            Ok(vec![(GpxKind::Track, polyline.clone())])
+        );
+
+        let polyline = "angrIk~inAklB}{AsAcB";
+        let mut gpx = String::new();
+        to_gpx_3d(5, 2, GpxKind::Route, [polyline], &mut gpx).unwrap();
+        assert_eq!(
+            parse_gpx_3d(5, 2, &gpx),
+            Ok(vec![(GpxKind::Route, polyline.to_string())])
+        );
+    }
+
+    #[test]
+    fn tolerant_reader() {
+        let gpx = r#"<?xml version="1.0"?>
+<!DOCTYPE gpx>
+<!-- <trkseg><trkpt lat="1" lon="1"/></trkseg> -->
+<g:gpx xmlns:g="http://www.topografix.com/GPX/1/1" xmlns:ext="urn:ext">
+  <g:metadata><g:name><![CDATA[<rte>]]></g:name><g:time>2023-11-14T22:13:20Z</g:time></g:metadata>
+  <g:wpt lat="10" lon="10"><g:ele>100</g:ele></g:wpt>
+  <g:trk>
+    <g:name>Morning ride</g:name>
+    <g:trkseg>
+      <g:trkpt g:lon = '12.99958' note="a > b" lat="55.58513">
+        <g:ele> 17.5 </g:ele>
+        <g:extensions><ext:data><g:ele>1000</g:ele><g:time>bad</g:time></ext:data></g:extensions>
+      </g:trkpt>
+      <g:trkpt lat="55.6" lon="13.0"><g:time>2023-11-14T22:13:20Z</g:time></g:trkpt>
+      <g:extensions/>
+    </g:trkseg>
+    <g:trkseg/>
+  </g:trk>
+  <g:rte><g:rtept lat="-33.86785" lon="151.20699"/><g:trkpt lat="1" lon="1"/></g:rte>
+</g:gpx>"#;
+        assert_eq!(
+            parse_gpx(5, gpx),
+            Ok(vec![
+                (
+                    GpxKind::Track,
+                    encode(5, [(55.58513, 12.99958), (55.6, 13.0)])
+                ),
+                (GpxKind::Track, String::new()),
+                (GpxKind::Route, encode(5, [(-33.86785, 151.20699)])),
+            ])
+        );
+        assert_eq!(
+            parse_gpx_3d(5, 2, gpx),
+            Ok(vec![
+                (
+                    GpxKind::Track,
+                    encode_3d(5, 2, [(55.58513, 12.99958, 17.5), (55.6, 13.0, 0.0)])
+                ),
+                (GpxKind::Track, String::new()),
+                (
+                    GpxKind::Route,
+                    encode_3d(5, 2, [(-33.86785, 151.20699, 0.0)])
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn times() {
+        for (time, expected) in [
+            ("1970-01-01T00:00:00Z", Some(0.0)),
+            ("2023-11-14T22:13:20Z", Some(1_700_000_000.0)),
+            ("2023-11-14T22:13:20", Some(1_700_000_000.0)),
+            ("2023-11-14t22:13:20.25z", Some(1_700_000_000.25)),
+            ("2023-11-14T17:13:20-05:00", Some(1_700_000_000.0)),
+            ("2000-02-29T00:00:00Z", Some(951_782_400.0)),
+            ("1969-12-31T23:59:59.5Z", Some(-0.5)),
+            ("2023-13-14T22:13:20Z", None),
+            ("2023-11-14T22:13Z", None),
+            ("2023-11-14T22:13:20+0500", None),
+            ("2023-11-14T22:13:20.Z", None),
+            ("+023-11-14T22:13:20Z", None),
+        ] {
+            assert_eq!(parse_time(time), expected, "{time}");
+        }
+        for days in [-719468, -1, 0, 59, 10956, 19675, 2932896] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let wrap = |points: &str| format!("<gpx><trk><trkseg>{points}</trkseg></trk></gpx>");
+        assert_eq!(
+            parse_gpx(5, &wrap(r#"<trkpt lat="1"/>"#)),
+            Err(GpxError::InvalidPoint { offset: 18 })
+        );
+        assert_eq!(
+            parse_gpx(5, &wrap(r#"<trkpt lat="91" lon="0"/>"#)),
+            Err(GpxError::InvalidPoint { offset: 18 })
+        );
+        let gpx = wrap(r#"<trkpt lat="1" lon="1"><ele>high</ele></trkpt>"#);
+        assert_eq!(
+            parse_gpx(5, &gpx),
+            Ok(vec![(GpxKind::Track, encode(5, [(1.0, 1.0)]))])
+        );
+        assert_eq!(
+            parse_gpx_3d(5, 2, &gpx),
+            Err(GpxError::InvalidPoint { offset: 46 })
+        );
+
+        assert_eq!(
+            parse_gpx(5, "<gpx><trk><trkseg"),
+            Err(GpxError::Syntax { offset: 10 })
+        );
+        assert_eq!(
+            parse_gpx(5, "<gpx><!-- <trk>"),
+            Err(GpxError::Syntax { offset: 5 })
+        );
+        assert_eq!(
+            parse_gpx(5, "<gpx><rte>"),
+            Err(GpxError::Syntax { offset: 10 })
+        );
+        assert_eq!(parse_gpx(5, ""), Ok(vec![]));
+    }
+}
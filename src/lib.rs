@@ -1,3 +1,80 @@
+pub mod bing;
+pub mod esri;
+pub mod flexible;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod twkb;
+
+mod archive;
+mod base64url;
+mod binary_dod;
+mod binary_header;
+mod binary_packed;
+mod canonical;
+mod checked;
+mod checksum;
+#[cfg(feature = "geo-types")]
+mod geo;
+mod geojson;
+mod gpx;
+mod origin;
+mod polyline3d;
+mod polyline64;
+mod polyline_nd;
+mod scale;
+mod transcode;
+mod wkb;
+mod wkt;
+
+pub use archive::{ArchiveIter, decode_archive, encode_archive};
+pub use base64url::{
+    BinaryPolylineIterBase64Url, decode_binary_base64url, encode_binary_base64url,
+};
+pub use binary_dod::{BinaryPolylineIterDod, decode_binary_dod, encode_binary_dod};
+pub use binary_header::{
+    BinaryHeader, BinaryHeaderError, decode_binary_auto, encode_binary_with_header,
+};
+pub use binary_packed::{
+    BLOCK_SIZE, BinaryPolylineIterPacked, binary_to_packed, decode_binary_packed,
+    encode_binary_packed, packed_to_binary,
+};
+pub use canonical::{canonicalize, canonicalize_binary, is_canonical, is_canonical_binary};
+pub use checked::{
+    CanonicalPolicy, DecodeError, DecodeErrorKind, EncodeError, EncodeErrorKind, RangePolicy,
+    try_decode, try_decode_binary, try_decode_binary_with, try_decode_with, try_encode,
+    try_encode_binary, try_encode_binary_with, try_encode_with,
+};
+pub use checksum::{ChecksumError, decode_binary_checked, encode_binary_checked};
+#[cfg(feature = "geo-types")]
+pub use geo::{
+    ToPolyline, decode_coords, decode_linestring, encode_coords, encode_linestring,
+    encode_multipoint,
+};
+pub use geojson::{
+    FloatFormat, GeoJsonError, parse_geojson, to_geojson_linestring, to_geojson_linestring_with,
+};
+pub use gpx::{
+    GpxError, GpxKind, parse_gpx, parse_gpx_3d, parse_gpx_nd, to_gpx, to_gpx_3d, to_gpx_nd,
+};
+pub use origin::{
+    Origin, decode_binary_with_origin, decode_with_origin, encode_binary_with_origin,
+    encode_with_origin,
+};
+pub use polyline_nd::{PolylineIterNd, decode_nd, encode_nd};
+pub use polyline3d::{PolylineIter3d, decode_3d, encode_3d};
+pub use polyline64::{
+    BinaryPolylineIter64, PolylineIter64, decode_binary64, decode64, encode_binary64, encode64,
+};
+pub use scale::Scale;
+pub use transcode::{binary_to_text, text_to_binary, transcode, transcode_binary};
+pub use wkb::{
+    ByteOrder, WkbError, WkbOptions, parse_wkb, to_wkb_linestring, to_wkb_multilinestring,
+};
+pub use wkt::{
+    WktError, parse_wkt, to_wkt_linestring, to_wkt_linestring_with, to_wkt_multilinestring,
+    to_wkt_multilinestring_with,
+};
+
 /// Iterator over geographic coordinates (latitude/longitude pairs) decoded from a polyline-encoded string.
 ///
 /// Supports both formats:
@@ -22,16 +99,15 @@
 /// // Count points without collecting them
 /// assert_eq!(polyline_iter::decode(5, "avs_iB}xlxWissBw|zEu``AsxgCyoaAm_z@").count(), 4);
 ///
-/// // Iterator approach allows to transcode polyline to another precision without intermediate allocations.
-/// let polyline5 = polyline_iter::encode(5, polyline_iter::decode(6, "avs_iB}xlxWissBw|zEu``AsxgCyoaAm_z@"));
-/// assert_eq!(polyline5, "cngrIk~inAgtJw~TeoEwtL{sE{{D");
+/// // Transcode polyline to another precision, rounding each point exactly as if it was encoded with that precision.
+/// let polyline5 = polyline_iter::transcode(6, 5, "avs_iB}xlxWissBw|zEu``AsxgCyoaAm_z@").unwrap();
 /// assert_eq!(
 ///     polyline_iter::decode(5, &polyline5).collect::<Vec<_>>(),
 ///     vec![
 ///         (55.58514, 12.99958),
-///         (55.64486, 13.11218),
-///         (55.67817, 13.18222),
-///         (55.71223, 13.21244)
+///         (55.64485, 13.11219),
+///         (55.67816, 13.18223),
+///         (55.71222, 13.21244)
 ///     ],
 /// );
 /// ```
@@ -50,11 +126,17 @@ impl<'a> PolylineIter<'a> {
     #[inline(always)]
     pub fn new(precision: u8, polyline: &'a str) -> Self {
         assert!(precision <= 7, "i32 can hold up to 180 * 10^7");
+        Self::with_scale(Scale::from_precision(precision), polyline)
+    }
+
+    /// Creates a new iterator over points decoded from a polyline encoded with an arbitrary [`Scale`].
+    #[inline(always)]
+    pub fn with_scale(scale: Scale, polyline: &'a str) -> Self {
         PolylineIter {
             polyline: polyline.as_bytes(),
             lat: 0,
             lon: 0,
-            scale: 10.0_f64.powi(precision as i32),
+            scale: scale.factor(),
         }
     }
 
@@ -122,6 +204,7 @@ impl Iterator for PolylineIter<'_> {
 /// This is a convenience function that wraps [`PolylineIter::new()`] and returns an iterator over points.
 /// The precision parameter specifies the number of decimal places in the coordinates (5 for polyline5,
 /// 6 for polyline6), with a maximum value of 7 which corresponds to ~1cm precision at the equator.
+/// Use [`decode64()`] for higher precision.
 ///
 /// ```
 /// use polyline_iter::decode;
@@ -150,9 +233,34 @@ pub fn decode(precision: u8, polyline: &str) -> PolylineIter<'_> {
     PolylineIter::new(precision, polyline)
 }
 
+/// Decodes a polyline encoded with an arbitrary [`Scale`], see [`encode_scaled()`].
+///
+/// ```
+/// use polyline_iter::Scale;
+///
+/// // 2^20 fixed-point coordinates
+/// let scale = Scale::from_factor((1 << 20) as f64);
+/// let polyline = polyline_iter::encode_scaled(scale, [(55.5, 13.25), (55.75, 13.125)]);
+/// assert_eq!(
+///     polyline_iter::decode_scaled(scale, &polyline).collect::<Vec<_>>(),
+///     [(55.5, 13.25), (55.75, 13.125)]
+/// );
+/// ```
+#[inline(always)]
+pub fn decode_scaled(scale: Scale, polyline: &str) -> PolylineIter<'_> {
+    PolylineIter::with_scale(scale, polyline)
+}
+
 /// Encodes a sequence of points (latitude, longitude pairs) into a polyline string with the given precision.
 /// The precision parameter specifies the number of decimal places in the coordinates (5 for polyline5,
 /// 6 for polyline6), with a maximum value of 7 which corresponds to ~1cm precision at the equator.
+/// Use [`encode64()`] for higher precision.
+///
+/// Every point is rounded to the precision on its own and the changes are taken between the rounded values,
+/// so decoded points don't drift and any tail of the output can be produced by [`encode_with_origin()`].
+///
+/// NaN and infinite coordinates are not checked and produce meaningless output,
+/// use [`try_encode()`] to validate the input.
 ///
 /// ```
 /// // Encode an array of latitude/longitude coordinates with precision 5 (standard for Google Maps)
@@ -174,14 +282,40 @@ pub fn decode(precision: u8, polyline: &str) -> PolylineIter<'_> {
 /// ```
 pub fn encode(precision: u8, points: impl IntoIterator<Item = (f64, f64)>) -> String {
     assert!(precision <= 7, "i32 can hold up to 180 * 10^7");
+    encode_scaled(Scale::from_precision(precision), points)
+}
+
+/// Encodes a sequence of points into a polyline string, multiplying coordinates by an arbitrary [`Scale`]
+/// instead of `10^precision`. The output is only decodable with the same scale.
+///
+/// ```
+/// use polyline_iter::Scale;
+///
+/// // Projected meters at centimeter resolution
+/// let scale = Scale::from_factor(100.0);
+/// let polyline = polyline_iter::encode_scaled(scale, [(6_178_512.25, 1_447_058.5)]);
+/// assert_eq!(polyline_iter::decode_scaled(scale, &polyline).next(), Some((6_178_512.25, 1_447_058.5)));
+///
+/// // Powers of ten are the same as precision
+/// let points = [(55.58513, 12.99958), (55.61461, 13.04627)];
+/// assert_eq!(polyline_iter::encode_scaled(Scale::from_precision(5), points), polyline_iter::encode(5, points));
+/// ```
+pub fn encode_scaled(scale: Scale, points: impl IntoIterator<Item = (f64, f64)>) -> String {
+    encode_from(scale.factor(), (0, 0), points)
+}
 
-    let scale = 10.0_f64.powi(precision as i32);
+/// Encodes points as changes relative to `prev`, which is `(0, 0)` for the standard format.
+fn encode_from(
+    scale: f64,
+    mut prev: (i32, i32),
+    points: impl IntoIterator<Item = (f64, f64)>,
+) -> String {
     let mut result = String::with_capacity(16);
 
-    let mut prev = (0.0, 0.0);
     for point in points {
-        let lat_change = ((point.0 - prev.0) * scale).round() as i32;
-        let lon_change = ((point.1 - prev.1) * scale).round() as i32;
+        let point = scale_point(point, scale);
+        let lat_change = point.0.wrapping_sub(prev.0);
+        let lon_change = point.1.wrapping_sub(prev.1);
 
         varint32_encode5(zigzag_encode(lat_change), &mut result);
         varint32_encode5(zigzag_encode(lon_change), &mut result);
@@ -191,6 +325,18 @@ pub fn encode(precision: u8, points: impl IntoIterator<Item = (f64, f64)>) -> St
     result
 }
 
+/// Rounds a point multiplied by the scale to integers, saturating values that don't fit.
+///
+/// Changes are taken between rounded points rather than rounded themselves, so the decoder gets each point
+/// rounded independently, without accumulating errors, and the result doesn't depend on where the chain starts.
+#[inline(always)]
+pub(crate) fn scale_point(point: (f64, f64), scale: f64) -> (i32, i32) {
+    (
+        (point.0 * scale).round() as i32,
+        (point.1 * scale).round() as i32,
+    )
+}
+
 /// Encodes a sequence of points into a space-efficient binary format.
 ///
 /// This binary format stores 7 bits per byte instead of the 5 bits used by the standard polyline
@@ -223,14 +369,35 @@ pub fn encode(precision: u8, points: impl IntoIterator<Item = (f64, f64)>) -> St
 /// ```
 pub fn encode_binary(precision: u8, points: impl IntoIterator<Item = (f64, f64)>) -> Vec<u8> {
     assert!(precision <= 7, "i32 can hold up to 180 * 10^7");
+    encode_binary_scaled(Scale::from_precision(precision), points)
+}
 
-    let scale = 10.0_f64.powi(precision as i32);
+/// Encodes a sequence of points into the binary format with an arbitrary [`Scale`],
+/// see [`encode_binary()`] and [`encode_scaled()`].
+///
+/// ```
+/// use polyline_iter::Scale;
+///
+/// let scale = Scale::from_factor(3600.0); // arc-seconds
+/// let binary = polyline_iter::encode_binary_scaled(scale, [(55.5, 13.25)]);
+/// assert_eq!(polyline_iter::decode_binary_scaled(scale, &binary).next(), Some((55.5, 13.25)));
+/// ```
+pub fn encode_binary_scaled(scale: Scale, points: impl IntoIterator<Item = (f64, f64)>) -> Vec<u8> {
+    encode_binary_from(scale.factor(), (0, 0), points)
+}
+
+/// Binary version of [`encode_from`].
+fn encode_binary_from(
+    scale: f64,
+    mut prev: (i32, i32),
+    points: impl IntoIterator<Item = (f64, f64)>,
+) -> Vec<u8> {
     let mut result = Vec::with_capacity(16);
 
-    let mut prev = (0.0, 0.0);
     for point in points {
-        let lat_change = ((point.0 - prev.0) * scale).round() as i32;
-        let lon_change = ((point.1 - prev.1) * scale).round() as i32;
+        let point = scale_point(point, scale);
+        let lat_change = point.0.wrapping_sub(prev.0);
+        let lon_change = point.1.wrapping_sub(prev.1);
 
         // When storing 7 bits per byte, there are good chances that many of bits in the last byte will be unused.
         // By interleaving the bits of lat and lon changes, we sum up their significant bits and encode them together
@@ -266,6 +433,11 @@ pub fn decode_binary(precision: u8, polyline: &[u8]) -> BinaryPolylineIter<'_> {
     BinaryPolylineIter::new(precision, polyline)
 }
 
+/// Decodes points from binary data created by [`encode_binary_scaled()`].
+pub fn decode_binary_scaled(scale: Scale, polyline: &[u8]) -> BinaryPolylineIter<'_> {
+    BinaryPolylineIter::with_scale(scale, polyline)
+}
+
 /// Iterator over geographic coordinates decoded from binary polyline data.
 ///
 /// Created by [`decode_binary()`]. This iterator provides the same interface as
@@ -294,26 +466,23 @@ impl<'a> BinaryPolylineIter<'a> {
     #[inline(always)]
     pub fn new(precision: u8, polyline: &'a [u8]) -> Self {
         assert!(precision <= 7, "i32 can hold up to 180 * 10^7");
+        Self::with_scale(Scale::from_precision(precision), polyline)
+    }
+
+    /// Creates a new iterator over points decoded from binary data encoded with an arbitrary [`Scale`].
+    #[inline(always)]
+    pub fn with_scale(scale: Scale, polyline: &'a [u8]) -> Self {
         BinaryPolylineIter {
             polyline,
             lat: 0,
             lon: 0,
-            scale: 10.0_f64.powi(precision as i32),
+            scale: scale.factor(),
         }
     }
 
     #[inline(always)]
     fn varint_decode(&mut self) -> Option<u64> {
-        let mut result = 0;
-        for i in 0..self.polyline.len().min(9) {
-            let chunk = self.polyline[i] as u64;
-            result |= (chunk & 0x7f) << (i * 7); // no shift overflow as i < 5
-            if chunk & 0x80 == 0 {
-                self.polyline = &self.polyline[i + 1..];
-                return Some(result);
-            }
-        }
-        None
+        varint64_decode7(&mut self.polyline)
     }
 
     /// O(n) operation to count the number of points in the polyline without consuming the iterator.
@@ -335,8 +504,9 @@ impl Iterator for BinaryPolylineIter<'_> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let (lat_change, lon_change) = bitwise_split(self.varint_decode()?);
-        self.lat += zigzag_decode(lat_change);
-        self.lon += zigzag_decode(lon_change);
+        // Wrapping to avoid overflow panics on malformed input
+        self.lat = self.lat.wrapping_add(zigzag_decode(lat_change));
+        self.lon = self.lon.wrapping_add(zigzag_decode(lon_change));
         let lat = self.lat as f64 / self.scale;
         let lon = self.lon as f64 / self.scale;
         Some((lat, lon))
@@ -387,6 +557,27 @@ fn varint64_encode7(mut value: u64, buffer: &mut Vec<u8>) {
     buffer.push(value as u8);
 }
 
+/// Decodes a value stored by [`varint64_encode7()`] from the start of `data` and advances past it.
+/// Returns `None` if the data ends in the middle of the value.
+#[inline(always)]
+pub(crate) fn varint64_decode7(data: &mut &[u8]) -> Option<u64> {
+    let mut result = 0;
+    // 64 bits are stored in at most 10 bytes by 7 bits each, so the 10th byte can only be 0 or 1.
+    // Longer varints are never produced by the encoder and are treated as the end of the data.
+    for i in 0..data.len().min(10) {
+        let chunk = data[i] as u64;
+        if i == 9 && chunk > 1 {
+            return None;
+        }
+        result |= (chunk & 0x7f) << (i * 7); // no shift overflow as i < 10
+        if chunk & 0x80 == 0 {
+            *data = &data[i + 1..];
+            return Some(result);
+        }
+    }
+    None
+}
+
 /// Merges bits from two 32-bit integers into a 64-bit integer, shuffling bits from
 /// x = 'ABCD EFGH IJKL MNOP' and y = 'abcd efgh ijkl mnop' to 'aAbB cCdD eEfF gGhH iIjJ kKlL mMnN oOpP'.
 /// So if `x` and `y` have low number of significant bits, the result will have low number of significant bits.
@@ -427,6 +618,55 @@ fn perfect_unshuffle(mut x: u64) -> u64 {
     x
 }
 
+/// Benchmark polylines shared by the tests of all formats.
+#[cfg(test)]
+pub(crate) mod test_data {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    pub(crate) const MEDIUM_POLYLINE6: &str = include_str!("../benches/data/medium.polyline6");
+    pub(crate) const LONG_POLYLINE6: &str = include_str!("../benches/data/long.polyline6");
+
+    /// Lines with the poles, the antimeridian and the smallest changes at precision 6,
+    /// and an empty line between them, for round trips through the interchange formats.
+    pub(crate) const EDGE_LINES6: [&[(f64, f64)]; 3] = [
+        &[(90.0, -180.0), (-90.0, 180.0), (-89.999999, 179.999999)],
+        &[],
+        &[(0.000001, -0.000001), (-0.0, 0.0)],
+    ];
+
+    /// [`EDGE_LINES6`] encoded with precision 6.
+    pub(crate) fn edge_polylines6() -> Vec<String> {
+        EDGE_LINES6
+            .iter()
+            .map(|line| encode(6, line.iter().copied()))
+            .collect()
+    }
+
+    #[test]
+    fn size_comparison() {
+        // Text, binary, base64url, second-order, block-packed and archive sizes
+        let sizes = [MEDIUM_POLYLINE6, LONG_POLYLINE6].map(|polyline| {
+            let points: Vec<_> = decode(6, polyline).collect();
+            [
+                polyline.len(),
+                encode_binary(6, points.iter().copied()).len(),
+                encode_binary_base64url(6, points.iter().copied()).len(),
+                encode_binary_dod(6, points.iter().copied()).len(),
+                encode_binary_packed(6, points.iter().copied()).len(),
+                encode_archive(6, points.iter().copied()).len(),
+            ]
+        });
+        assert_eq!(
+            sizes,
+            [
+                [1901, 1408, 1878, 1214, 1423, 1306],
+                [26817, 20004, 26672, 17606, 20584, 16640]
+            ]
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -757,6 +997,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn custom_scale() {
+        let points = [(55.5, 13.25), (55.75, -13.125), (-0.5, 0.0)];
+        for factor in [1.0, 3600.0, (1 << 20) as f64, 0.5] {
+            let scale = Scale::from_factor(factor);
+            let polyline = encode_scaled(scale, points);
+            assert!(check_polyline(&polyline));
+            let binary = encode_binary_scaled(scale, points);
+            if factor >= 8.0 {
+                assert_eq!(decode_scaled(scale, &polyline).collect::<Vec<_>>(), points);
+                assert_eq!(
+                    decode_binary_scaled(scale, &binary).collect::<Vec<_>>(),
+                    points
+                );
+            }
+            assert_eq!(
+                decode_scaled(scale, &polyline).collect::<Vec<_>>(),
+                decode_binary_scaled(scale, &binary).collect::<Vec<_>>()
+            );
+        }
+
+        // Coarse scale rounds to the nearest multiple of `1 / factor`
+        let scale = Scale::from_factor(0.5);
+        let polyline = encode_scaled(scale, [(55.5, 13.25)]);
+        assert_eq!(decode_scaled(scale, &polyline).next(), Some((56.0, 14.0)));
+
+        for precision in 0..=7 {
+            let polyline = "angrIk~inAgwDybH_|D_{KeoEwtLozFo`Gre@tcA";
+            let scale = Scale::from_precision(precision);
+            assert!(decode(precision, polyline).eq(decode_scaled(scale, polyline)));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn bad_scale() {
+        Scale::from_factor(f64::NAN);
+    }
+
     #[test]
     fn encode_decode_binary() {
         let polyline = "angrIk~inAgwDybH_|D_{KeoEwtLozFo`Gre@tcA";
@@ -770,4 +1049,50 @@ mod tests {
 
         assert_eq!(decode_binary(5, &compressed).count(), points.len());
     }
+
+    #[test]
+    fn binary_extremes() {
+        // Changes of i32::MAX and i32::MIN are interleaved into almost all 64 bits, which take 10 bytes
+        let max = i32::MAX as f64;
+        let min = i32::MIN as f64;
+        assert_eq!(encode_binary(0, [(max, min)]).len(), 10);
+
+        let points = [(max, min), (0.0, -1.0), (min, 0.0), (-1.0, max)];
+        let binary = encode_binary(0, points);
+        let iter = decode_binary(0, &binary);
+        assert_eq!(iter.len(), points.len());
+        assert_eq!(iter.collect::<Vec<_>>(), points);
+
+        let points = [(214.7483647, -214.7483648), (0.0, -0.0000001)];
+        assert_eq!(
+            decode_binary(7, &encode_binary(7, points)).collect::<Vec<_>>(),
+            points
+        );
+    }
+
+    #[test]
+    fn binary_overlong_varint() {
+        let valid = encode_binary(0, [(i32::MAX as f64, i32::MIN as f64)]);
+        assert_eq!(valid[9], 0x01);
+
+        // The 10th byte has bits above 64
+        let mut overlong = valid.clone();
+        overlong[9] = 0x02;
+        assert_eq!(decode_binary(0, &overlong).next(), None);
+
+        // The 10th byte has the continuation bit, so the varint would take 11 bytes
+        let mut overlong = valid.clone();
+        overlong[9] = 0x81;
+        overlong.push(0x00);
+        assert_eq!(decode_binary(0, &overlong).next(), None);
+        assert_eq!(decode_binary(0, &[0xFF; 16]).next(), None);
+
+        // Previous points are still decoded
+        let mut binary = encode_binary(5, [(55.58513, 12.99958)]);
+        binary.extend_from_slice(&[0xFF; 11]);
+        assert_eq!(
+            decode_binary(5, &binary).collect::<Vec<_>>(),
+            [(55.58513, 12.99958)]
+        );
+    }
 }
@@ -0,0 +1,238 @@
+//! Encoding relative to a known non-zero origin, e.g. a tile corner or the last point of the previous chunk.
+
+use crate::{BinaryPolylineIter, PolylineIter, encode_binary_from, encode_from, scale_point};
+
+/// Starting point of the delta chain, which is `(0, 0)` in the standard format.
+///
+/// Can be created from `(f64, f64)` latitude/longitude or from `(i32, i32)` already multiplied
+/// by the scale, exactly as they are stored in the polyline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Origin {
+    /// Latitude and longitude in degrees.
+    Point(f64, f64),
+    /// Latitude and longitude multiplied by the scale.
+    Scaled(i32, i32),
+}
+
+impl Origin {
+    /// Returns the origin multiplied by the scale, as used by the encoder and decoder state.
+    fn scaled(self, scale: f64) -> (i32, i32) {
+        match self {
+            Origin::Point(lat, lon) => scale_point((lat, lon), scale),
+            Origin::Scaled(lat, lon) => (lat, lon),
+        }
+    }
+}
+
+impl From<(f64, f64)> for Origin {
+    fn from((lat, lon): (f64, f64)) -> Self {
+        Origin::Point(lat, lon)
+    }
+}
+
+impl From<(i32, i32)> for Origin {
+    fn from((lat, lon): (i32, i32)) -> Self {
+        Origin::Scaled(lat, lon)
+    }
+}
+
+/// Encodes points into a polyline string, starting the delta chain from `origin` instead of `(0, 0)`.
+///
+/// The origin and the points are rounded to the precision before taking the changes, exactly as in [`encode()`](crate::encode).
+/// So if the origin is the last point of the previous chunk, the result is exactly the tail of
+/// the polyline encoded as a whole, even for points between the grid nodes, and chunks can be concatenated back together.
+///
+/// ```
+/// let points = [(55.58513, 12.99958), (55.61461, 13.04627), (55.64485, 13.11219)];
+/// let whole = polyline_iter::encode(5, points);
+/// let head = polyline_iter::encode(5, points[..1].iter().copied());
+/// let tail = polyline_iter::encode_with_origin(5, points[0], points[1..].iter().copied());
+/// assert_eq!(head + &tail, whole);
+///
+/// // Tile-local geometry is much shorter than absolute coordinates
+/// let tile_corner = (55.5, 12.9);
+/// assert!(polyline_iter::encode_with_origin(5, tile_corner, points).len() < whole.len());
+/// ```
+pub fn encode_with_origin(
+    precision: u8,
+    origin: impl Into<Origin>,
+    points: impl IntoIterator<Item = (f64, f64)>,
+) -> String {
+    assert!(precision <= 7, "i32 can hold up to 180 * 10^7");
+    let scale = 10.0_f64.powi(precision as i32);
+    encode_from(scale, origin.into().scaled(scale), points)
+}
+
+/// Decodes a polyline created by [`encode_with_origin()`] with the same origin.
+///
+/// ```
+/// let polyline = "angrIk~inAgwDybH_|D_{K";
+/// let points: Vec<_> = polyline_iter::decode(5, polyline).collect();
+///
+/// // "gwDybH_|D_{K" is the tail after the first point "angrIk~inA"
+/// let tail: Vec<_> = polyline_iter::decode_with_origin(5, points[0], "gwDybH_|D_{K").collect();
+/// assert_eq!(tail, points[1..]);
+///
+/// // The same with the origin as scaled integers
+/// let tail: Vec<_> = polyline_iter::decode_with_origin(5, (5558513, 1299958), "gwDybH_|D_{K").collect();
+/// assert_eq!(tail, points[1..]);
+/// ```
+pub fn decode_with_origin(
+    precision: u8,
+    origin: impl Into<Origin>,
+    polyline: &str,
+) -> PolylineIter<'_> {
+    let mut iter = PolylineIter::new(precision, polyline);
+    (iter.lat, iter.lon) = origin.into().scaled(iter.scale);
+    iter
+}
+
+/// Binary version of [`encode_with_origin()`], see [`encode_binary()`](crate::encode_binary).
+///
+/// ```
+/// let points = [(55.58513, 12.99958), (55.61461, 13.04627), (55.64485, 13.11219)];
+/// let whole = polyline_iter::encode_binary(5, points);
+/// let tail = polyline_iter::encode_binary_with_origin(5, points[0], points[1..].iter().copied());
+/// assert!(whole.ends_with(&tail));
+/// ```
+pub fn encode_binary_with_origin(
+    precision: u8,
+    origin: impl Into<Origin>,
+    points: impl IntoIterator<Item = (f64, f64)>,
+) -> Vec<u8> {
+    assert!(precision <= 7, "i32 can hold up to 180 * 10^7");
+    let scale = 10.0_f64.powi(precision as i32);
+    encode_binary_from(scale, origin.into().scaled(scale), points)
+}
+
+/// Decodes binary data created by [`encode_binary_with_origin()`] with the same origin.
+pub fn decode_binary_with_origin(
+    precision: u8,
+    origin: impl Into<Origin>,
+    polyline: &[u8],
+) -> BinaryPolylineIter<'_> {
+    let mut iter = BinaryPolylineIter::new(precision, polyline);
+    (iter.lat, iter.lon) = origin.into().scaled(iter.scale);
+    iter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, decode_binary, encode, encode_binary};
+    use pretty_assertions::assert_eq;
+
+    const POLYLINE: &str = "gzkgiBgwreX{@sI~HcBwBoi@sXvBsIcBgJSwGg@wGg@cG{@{JoAwGSkC{@ce@gOwj@oKsb@cBoFz@gEjC?~RRb[f@v[Sz@kHnAoA_l@SsIR?";
+
+    #[test]
+    fn split_text() {
+        let points: Vec<_> = decode(6, POLYLINE).collect();
+        for split in 1..points.len() {
+            let head = encode(6, points[..split].iter().copied());
+            let tail = encode_with_origin(6, points[split - 1], points[split..].iter().copied());
+            assert_eq!(head.clone() + &tail, POLYLINE);
+
+            // Decoding the tail with the last point of the head gives the same points as the whole polyline
+            let last = decode(6, &head).last().unwrap();
+            assert_eq!(last, points[split - 1]);
+            assert_eq!(
+                decode_with_origin(6, last, &tail).collect::<Vec<_>>(),
+                points[split..]
+            );
+            assert_eq!(
+                decode_with_origin(6, last, &tail).count(),
+                points.len() - split
+            );
+        }
+    }
+
+    #[test]
+    fn split_binary() {
+        let points: Vec<_> = decode(6, POLYLINE).collect();
+        let whole = encode_binary(6, points.iter().copied());
+        for split in 1..points.len() {
+            let head = encode_binary(6, points[..split].iter().copied());
+            let tail =
+                encode_binary_with_origin(6, points[split - 1], points[split..].iter().copied());
+            assert_eq!([head.clone(), tail.clone()].concat(), whole);
+
+            let last = decode_binary(6, &head).last().unwrap();
+            assert_eq!(
+                decode_binary_with_origin(6, last, &tail).collect::<Vec<_>>(),
+                points[split..]
+            );
+        }
+    }
+
+    #[test]
+    fn sub_precision_origin() {
+        // The origin is rounded to 0.00001, so the first change is 1 rather than 1.5 rounded to 2
+        let origin = (0.000005, -0.000005);
+        let points = [(0.00002, -0.00002), (0.00003, 0.0)];
+        let polyline = encode_with_origin(5, origin, points);
+        assert_eq!(polyline, encode_with_origin(5, (1, -1), points));
+        assert_eq!(
+            decode_with_origin(5, origin, &polyline).collect::<Vec<_>>(),
+            points
+        );
+        let binary = encode_binary_with_origin(5, origin, points);
+        assert_eq!(
+            decode_binary_with_origin(5, origin, &binary).collect::<Vec<_>>(),
+            points
+        );
+    }
+
+    #[test]
+    fn off_grid_points() {
+        // Taking changes between unrounded points would give `_pR` instead of `apR` for the second chunk
+        let points = [
+            (0.123456, 0.654321),
+            (0.223454, 0.754329),
+            (0.323457, 0.854322),
+        ];
+        let whole = encode(5, points);
+        let whole_binary = encode_binary(5, points);
+        for split in 1..points.len() {
+            let head = encode(5, points[..split].iter().copied());
+            let tail = encode_with_origin(5, points[split - 1], points[split..].iter().copied());
+            assert_eq!(head + &tail, whole);
+
+            let head = encode_binary(5, points[..split].iter().copied());
+            let tail =
+                encode_binary_with_origin(5, points[split - 1], points[split..].iter().copied());
+            assert_eq!([head, tail].concat(), whole_binary);
+        }
+        // Each point is rounded on its own
+        let expected = [(0.12346, 0.65432), (0.22345, 0.75433), (0.32346, 0.85432)];
+        assert_eq!(decode(5, &whole).collect::<Vec<_>>(), expected);
+        assert_eq!(
+            decode_binary(5, &whole_binary).collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn scaled_origin() {
+        let origin = (55_500_000, 12_900_000);
+        let points = [(55.58513, 12.99958), (55.61461, 13.04627)];
+        let polyline = encode_with_origin(6, origin, points);
+        assert_eq!(polyline, encode_with_origin(6, (55.5, 12.9), points));
+        assert_eq!(
+            decode_with_origin(6, origin, &polyline).collect::<Vec<_>>(),
+            points
+        );
+
+        let binary = encode_binary_with_origin(6, origin, points);
+        assert_eq!(
+            decode_binary_with_origin(6, origin, &binary).collect::<Vec<_>>(),
+            points
+        );
+
+        // Zero origin is the standard format
+        assert_eq!(encode_with_origin(6, (0, 0), points), encode(6, points));
+        assert_eq!(
+            encode_binary_with_origin(6, (0.0, 0.0), points),
+            encode_binary(6, points)
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! Polylines with elevation, where each point is encoded as three varints: latitude, longitude and elevation.
+//! This format is used by OpenRouteService and several bike-routing engines.
+
+use crate::{varint32_encode5, zigzag_decode, zigzag_encode};
+
+/// Iterator over `(latitude, longitude, elevation)` triples decoded from a polyline-encoded string.
+///
+/// ```
+/// let polyline = "angrIk~inAklB}{AsAcB";
+/// let points: Vec<_> = polyline_iter::decode_3d(5, 2, polyline).collect();
+/// assert_eq!(points, [(55.58513, 12.99958, 17.5), (55.6, 13.0, 18.0)]);
+/// ```
+pub struct PolylineIter3d<'a> {
+    polyline: &'a [u8],
+    scale: f64,
+    elevation_scale: f64,
+    /// Last processed latitude, multiplied by the scale.
+    lat: i32,
+    /// Last processed longitude, multiplied by the scale.
+    lon: i32,
+    /// Last processed elevation, multiplied by the elevation scale.
+    elevation: i32,
+}
+
+impl<'a> PolylineIter3d<'a> {
+    /// Creates a new iterator over points decoded from a polyline with elevation.
+    /// The elevation precision is the number of decimal places of elevation, which is 2 for OpenRouteService.
+    #[inline(always)]
+    pub fn new(precision: u8, elevation_precision: u8, polyline: &'a str) -> Self {
+        assert!(precision <= 7, "i32 can hold up to 180 * 10^7");
+        assert!(
+            elevation_precision <= 5,
+            "i32 can hold elevation up to 21474 * 10^5"
+        );
+        PolylineIter3d {
+            polyline: polyline.as_bytes(),
+            scale: 10.0_f64.powi(precision as i32),
+            elevation_scale: 10.0_f64.powi(elevation_precision as i32),
+            lat: 0,
+            lon: 0,
+            elevation: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn varint_decode(&mut self) -> Option<u32> {
+        let mut result = 0;
+        for i in 0..self.polyline.len().min(7) {
+            // Casting here to i32 here to provide bad value instead of overflow panicking on bad input.
+            let chunk = (self.polyline[i] as i32) - 63;
+            result |= (chunk & 0x1f) << (i * 5); // no shift overflow as i < 7
+            if chunk & 0x20 == 0 {
+                self.polyline = &self.polyline[i + 1..];
+                return Some(result as u32);
+            }
+        }
+        None
+    }
+
+    fn varint_count(&self) -> usize {
+        self.polyline
+            .iter()
+            .filter(|&&byte| byte.wrapping_sub(63) & 0x20 == 0)
+            .count()
+    }
+
+    /// O(n) operation to count the number of points in the polyline without consuming the iterator.
+    pub fn len(&self) -> usize {
+        self.varint_count() / 3 // Each point has 3 numbers
+    }
+
+    /// Checks if the polyline contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Strict O(n) check that the remaining polyline consists only of complete points,
+    /// i.e. the number of varints is a multiple of 3 and the last varint is not truncated.
+    /// Decoding a polyline with 2D points as 3D usually fails this check.
+    ///
+    /// ```
+    /// assert!(polyline_iter::decode_3d(5, 2, "angrIk~inAklB}{AsAcB").is_complete());
+    /// // 2D polyline with 2 points has 4 varints
+    /// assert!(!polyline_iter::decode_3d(5, 2, "angrIk~inAgwDybH").is_complete());
+    /// ```
+    pub fn is_complete(&self) -> bool {
+        let terminated = self
+            .polyline
+            .last()
+            .is_none_or(|&byte| byte.wrapping_sub(63) & 0x20 == 0);
+        terminated && self.varint_count() % 3 == 0
+    }
+}
+
+impl Iterator for PolylineIter3d<'_> {
+    type Item = (f64, f64, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let lat_change = self.varint_decode()?;
+        let lon_change = self.varint_decode()?;
+        let elevation_change = self.varint_decode()?;
+        // Wrapping to avoid overflow panics on malformed input
+        self.lat = self.lat.wrapping_add(zigzag_decode(lat_change));
+        self.lon = self.lon.wrapping_add(zigzag_decode(lon_change));
+        self.elevation = self.elevation.wrapping_add(zigzag_decode(elevation_change));
+        Some((
+            self.lat as f64 / self.scale,
+            self.lon as f64 / self.scale,
+            self.elevation as f64 / self.elevation_scale,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // There are at least polyline.len() / 18 points as each i32 is encoded in 5 bits per char.
+        // And at most polyline.len() / 3 points if each number (3 per point) is encoded only by a single char.
+        let len = self.polyline.len();
+        (len / 18, Some(len / 3))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+/// Decodes a polyline with elevation into an iterator over `(latitude, longitude, elevation)` triples.
+///
+/// ```
+/// // OpenRouteService uses precision 5 for coordinates and 2 for elevation
+/// let iter = polyline_iter::decode_3d(5, 2, "angrIk~inAklB}{AsAcB");
+/// assert_eq!(iter.len(), 2);
+/// assert_eq!(iter.last(), Some((55.6, 13.0, 18.0)));
+/// ```
+#[inline(always)]
+pub fn decode_3d(precision: u8, elevation_precision: u8, polyline: &str) -> PolylineIter3d<'_> {
+    PolylineIter3d::new(precision, elevation_precision, polyline)
+}
+
+/// Encodes a sequence of `(latitude, longitude, elevation)` triples into a polyline string.
+///
+/// ```
+/// let points = [(55.58513, 12.99958, 17.5), (55.6, 13.0, 18.0)];
+/// assert_eq!(polyline_iter::encode_3d(5, 2, points), "angrIk~inAklB}{AsAcB");
+/// ```
+pub fn encode_3d(
+    precision: u8,
+    elevation_precision: u8,
+    points: impl IntoIterator<Item = (f64, f64, f64)>,
+) -> String {
+    assert!(precision <= 7, "i32 can hold up to 180 * 10^7");
+    assert!(
+        elevation_precision <= 5,
+        "i32 can hold elevation up to 21474 * 10^5"
+    );
+
+    let scale = 10.0_f64.powi(precision as i32);
+    let elevation_scale = 10.0_f64.powi(elevation_precision as i32);
+    let mut result = String::with_capacity(16);
+
+    let mut prev = (0, 0, 0);
+    for point in points {
+        let point = (
+            (point.0 * scale).round() as i32,
+            (point.1 * scale).round() as i32,
+            (point.2 * elevation_scale).round() as i32,
+        );
+        let lat_change = point.0.wrapping_sub(prev.0);
+        let lon_change = point.1.wrapping_sub(prev.1);
+        let elevation_change = point.2.wrapping_sub(prev.2);
+
+        varint32_encode5(zigzag_encode(lat_change), &mut result);
+        varint32_encode5(zigzag_encode(lon_change), &mut result);
+        varint32_encode5(zigzag_encode(elevation_change), &mut result);
+
+        prev = point;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn empty() {
+        assert_eq!(encode_3d(5, 2, []), "");
+        assert_eq!(decode_3d(5, 2, "").next(), None);
+        assert!(decode_3d(5, 2, "").is_empty());
+        assert!(decode_3d(5, 2, "").is_complete());
+    }
+
+    #[test]
+    fn round_trip() {
+        let points = [
+            (49.41864, 8.67487, 113.25),
+            (49.41898, 8.67603, 114.0),
+            (49.41901, 8.67631, 112.7),
+            (-54.90653, -65.99208, -10.99),
+        ];
+        let polyline = encode_3d(5, 2, points);
+        let mut iter = decode_3d(5, 2, &polyline);
+        assert!(iter.is_complete());
+        assert_eq!(iter.len(), 4);
+        assert!(iter.size_hint().0 <= 4 && iter.size_hint().1.unwrap() >= 4);
+        assert_eq!(iter.next(), Some(points[0]));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), points[1..]);
+
+        // Without elevation the coordinates are the same as in 2D polyline
+        let flat = points.map(|(lat, lon, _)| (lat, lon, 0.0));
+        let polyline = encode_3d(5, 2, flat);
+        assert_eq!(
+            decode_3d(5, 2, &polyline)
+                .map(|(lat, lon, _)| (lat, lon))
+                .collect::<Vec<_>>(),
+            decode(5, &encode(5, flat.map(|(lat, lon, _)| (lat, lon)))).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn elevation_precision() {
+        let points = [(55.58513, 12.99958, 17.56)];
+        assert_eq!(
+            decode_3d(5, 0, &encode_3d(5, 0, points)).next(),
+            Some((55.58513, 12.99958, 18.0))
+        );
+        assert_eq!(
+            decode_3d(5, 1, &encode_3d(5, 1, points)).next(),
+            Some((55.58513, 12.99958, 17.6))
+        );
+        assert_eq!(
+            decode_3d(6, 2, &encode_3d(6, 2, points)).next(),
+            Some((55.58513, 12.99958, 17.56))
+        );
+    }
+
+    #[test]
+    fn incomplete() {
+        let polyline = encode_3d(5, 2, [(55.58513, 12.99958, 17.5), (55.6, 13.0, 18.0)]);
+
+        // Missing elevation of the last point
+        let truncated = &polyline[..polyline.len() - 2];
+        assert!(!decode_3d(5, 2, truncated).is_complete());
+        assert_eq!(decode_3d(5, 2, truncated).len(), 1);
+        assert_eq!(decode_3d(5, 2, truncated).collect::<Vec<_>>().len(), 1);
+
+        // Last varint is cut in the middle
+        let truncated = &polyline[..polyline.len() - 1];
+        assert!(!decode_3d(5, 2, truncated).is_complete());
+    }
+
+    #[test]
+    fn malformed() {
+        // Bytes above 0x7F are neither terminators nor valid chunks, but don't panic
+        assert_eq!(decode_3d(5, 2, "é").len(), 0);
+        assert!(!decode_3d(5, 2, "é").is_complete());
+
+        // Accumulated coordinates wrap around instead of overflowing
+        let max = encode_3d(0, 0, [(i32::MAX as f64, 0.0, i32::MAX as f64)]);
+        assert_eq!(
+            decode_3d(0, 0, &max.repeat(2)).nth(1),
+            Some((-2.0, 0.0, -2.0))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn bad_elevation_precision() {
+        encode_3d(5, 6, []);
+    }
+}
@@ -0,0 +1,459 @@
+//! 64-bit variants of the text and binary codecs for precisions that don't fit into `i32`.
+
+use crate::{bitwise_merge, bitwise_split};
+
+/// Iterator over geographic coordinates decoded from a polyline-encoded string with 64-bit accumulators.
+///
+/// Works exactly like [`PolylineIter`](crate::PolylineIter), but keeps the state in `i64` and thus
+/// supports precision up to 16 decimal places in the encoded integers. For precision up to 7 both iterators
+/// produce the same points.
+///
+/// Note that `f64` has only 15-16 significant digits, so geographic coordinates round-trip through
+/// `(f64, f64)` points exactly only up to precision 13. Higher precision works only for small values,
+/// e.g. scaled planar coordinates, as the last decimal places of larger ones are lost to `f64` rounding.
+///
+/// ```
+/// let polyline = polyline_iter::encode64(9, [(55.712182117, 13.215615092)]);
+/// assert_eq!(polyline, "iuvpuwfBgfcxurW");
+/// assert_eq!(
+///     polyline_iter::decode64(9, &polyline).collect::<Vec<_>>(),
+///     [(55.712182117, 13.215615092)]
+/// );
+///
+/// // Polylines with precision up to 7 are compatible with 32-bit decoder
+/// let polyline = "avs_iB}xlxWissBw|zEu``AsxgCyoaAm_z@";
+/// assert!(polyline_iter::decode64(6, polyline).eq(polyline_iter::decode(6, polyline)));
+/// ```
+pub struct PolylineIter64<'a> {
+    polyline: &'a [u8],
+    scale: f64,
+    /// Last processed latitude, multiplied by the scale.
+    lat: i64,
+    /// Last processed longitude, multiplied by the scale.
+    lon: i64,
+}
+
+impl<'a> PolylineIter64<'a> {
+    /// Creates a new iterator over points decoded from a polyline.
+    /// The precision is the number of decimal places in the coordinates, up to 16,
+    /// see the note on `f64` rounding above.
+    #[inline(always)]
+    pub fn new(precision: u8, polyline: &'a str) -> Self {
+        assert!(precision <= 16, "i64 can hold up to 180 * 10^16");
+        PolylineIter64 {
+            polyline: polyline.as_bytes(),
+            lat: 0,
+            lon: 0,
+            scale: 10.0_f64.powi(precision as i32),
+        }
+    }
+
+    #[inline(always)]
+    fn varint_decode(&mut self) -> Option<u64> {
+        let mut result = 0;
+        // 64 bits are stored in at most 13 chars by 5 bits each
+        for i in 0..self.polyline.len().min(13) {
+            // Casting here to i64 here to provide bad value instead of overflow panicking on bad input.
+            let chunk = (self.polyline[i] as i64) - 63;
+            result |= (chunk & 0x1f) << (i * 5); // no shift overflow as i < 13
+            if chunk & 0x20 == 0 {
+                self.polyline = &self.polyline[i + 1..];
+                return Some(result as u64);
+            }
+        }
+        None
+    }
+
+    /// O(n) operation to count the number of points in the polyline without consuming the iterator.
+    pub fn len(&self) -> usize {
+        self.polyline
+            .iter()
+            .filter(|&&byte| byte.wrapping_sub(63) & 0x20 == 0)
+            .count()
+            / 2 // Each point has 2 numbers
+    }
+
+    /// Checks if the polyline contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.polyline
+            .iter()
+            .filter(|&&byte| byte.wrapping_sub(63) & 0x20 == 0)
+            .nth(1)
+            .is_none()
+    }
+}
+
+impl Iterator for PolylineIter64<'_> {
+    type Item = (f64, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let lat_change = self.varint_decode()?;
+        let lon_change = self.varint_decode()?;
+        // Wrapping to avoid overflow panics on malformed input
+        self.lat = self.lat.wrapping_add(zigzag64_decode(lat_change));
+        self.lon = self.lon.wrapping_add(zigzag64_decode(lon_change));
+        let lat = self.lat as f64 / self.scale;
+        let lon = self.lon as f64 / self.scale;
+        Some((lat, lon))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // There are at least polyline.len() / 26 points as each i64 is encoded in 5 bits per char.
+        // And at most polyline.len() / 2 points if each number (2 per point) is encoded only by a single char.
+        let len = self.polyline.len();
+        (len / 26, Some(len / 2))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+/// Decodes a polyline-encoded string into an iterator over geographic coordinates using 64-bit accumulators.
+///
+/// This is a convenience function that wraps [`PolylineIter64::new()`]. Unlike [`decode()`](crate::decode),
+/// the precision can be up to 16 decimal places, though only up to 13 is lossless for geographic coordinates.
+///
+/// ```
+/// let points: Vec<_> = polyline_iter::decode64(10, "s~kp`vl_@ugizbduF").collect();
+/// assert_eq!(points, [(55.7121821178, 13.2156150923)]);
+/// ```
+#[inline(always)]
+pub fn decode64(precision: u8, polyline: &str) -> PolylineIter64<'_> {
+    PolylineIter64::new(precision, polyline)
+}
+
+/// Encodes a sequence of points (latitude, longitude pairs) into a polyline string using 64-bit deltas.
+///
+/// Produces the same output as [`encode()`](crate::encode) for precision up to 7, and additionally
+/// supports precision up to 16 decimal places by using longer varints. Geographic coordinates round-trip
+/// exactly only up to precision 13, see [`PolylineIter64`].
+///
+/// ```
+/// let point = (55.71218211778275, 13.21561509233427);
+/// assert_eq!(polyline_iter::encode64(5, [point]), polyline_iter::encode(5, [point]));
+/// assert_eq!(polyline_iter::encode64(10, [point]), "s~kp`vl_@ugizbduF");
+/// ```
+pub fn encode64(precision: u8, points: impl IntoIterator<Item = (f64, f64)>) -> String {
+    assert!(precision <= 16, "i64 can hold up to 180 * 10^16");
+
+    let scale = 10.0_f64.powi(precision as i32);
+    let mut result = String::with_capacity(16);
+
+    let mut prev = (0, 0);
+    for point in points {
+        let point = (
+            (point.0 * scale).round() as i64,
+            (point.1 * scale).round() as i64,
+        );
+        let lat_change = point.0.wrapping_sub(prev.0);
+        let lon_change = point.1.wrapping_sub(prev.1);
+
+        varint64_encode5(zigzag64_encode(lat_change), &mut result);
+        varint64_encode5(zigzag64_encode(lon_change), &mut result);
+
+        prev = point;
+    }
+    result
+}
+
+/// Encodes a sequence of points into the binary format using 64-bit deltas.
+///
+/// Same as [`encode_binary()`](crate::encode_binary), but supports precision up to 16 by merging
+/// two 64-bit zigzag values into a single 128-bit varint.
+///
+/// ```
+/// let points = [(55.712182117, 13.215615092), (55.712182119, 13.215615090)];
+/// let binary = polyline_iter::encode_binary64(9, points);
+/// assert!(binary.len() < polyline_iter::encode64(9, points).len());
+/// assert_eq!(polyline_iter::decode_binary64(9, &binary).collect::<Vec<_>>(), points);
+/// ```
+pub fn encode_binary64(precision: u8, points: impl IntoIterator<Item = (f64, f64)>) -> Vec<u8> {
+    assert!(precision <= 16, "i64 can hold up to 180 * 10^16");
+
+    let scale = 10.0_f64.powi(precision as i32);
+    let mut result = Vec::with_capacity(16);
+
+    let mut prev = (0, 0);
+    for point in points {
+        let point = (
+            (point.0 * scale).round() as i64,
+            (point.1 * scale).round() as i64,
+        );
+        let lat_change = point.0.wrapping_sub(prev.0);
+        let lon_change = point.1.wrapping_sub(prev.1);
+
+        let interleaved = bitwise_merge64(zigzag64_encode(lat_change), zigzag64_encode(lon_change));
+        varint128_encode7(interleaved, &mut result);
+
+        prev = point;
+    }
+    result
+}
+
+/// Decodes points from binary data created by [`encode_binary64()`].
+pub fn decode_binary64(precision: u8, polyline: &[u8]) -> BinaryPolylineIter64<'_> {
+    BinaryPolylineIter64::new(precision, polyline)
+}
+
+/// Iterator over geographic coordinates decoded from binary polyline data with 64-bit deltas.
+///
+/// Created by [`decode_binary64()`]. Provides the same interface as [`BinaryPolylineIter`](crate::BinaryPolylineIter).
+pub struct BinaryPolylineIter64<'a> {
+    polyline: &'a [u8],
+    scale: f64,
+    /// Last processed latitude, multiplied by the scale.
+    lat: i64,
+    /// Last processed longitude, multiplied by the scale.
+    lon: i64,
+}
+
+impl<'a> BinaryPolylineIter64<'a> {
+    /// Creates a new iterator over points decoded from binary data created by [`encode_binary64()`].
+    /// The precision is the number of decimal places in the coordinates, up to 16.
+    #[inline(always)]
+    pub fn new(precision: u8, polyline: &'a [u8]) -> Self {
+        assert!(precision <= 16, "i64 can hold up to 180 * 10^16");
+        BinaryPolylineIter64 {
+            polyline,
+            lat: 0,
+            lon: 0,
+            scale: 10.0_f64.powi(precision as i32),
+        }
+    }
+
+    #[inline(always)]
+    fn varint_decode(&mut self) -> Option<u128> {
+        let mut result = 0;
+        // 128 bits are stored in at most 19 bytes by 7 bits each, so the 19th byte can only be from 0 to 3.
+        for i in 0..self.polyline.len().min(19) {
+            let chunk = self.polyline[i] as u128;
+            if i == 18 && chunk > 3 {
+                return None;
+            }
+            result |= (chunk & 0x7f) << (i * 7); // no shift overflow as i < 19
+            if chunk & 0x80 == 0 {
+                self.polyline = &self.polyline[i + 1..];
+                return Some(result);
+            }
+        }
+        None
+    }
+
+    /// O(n) operation to count the number of points in the polyline without consuming the iterator.
+    pub fn len(&self) -> usize {
+        self.polyline
+            .iter()
+            .filter(|&&byte| byte & 0x80 == 0)
+            .count()
+    }
+
+    /// Checks if the polyline contains no points.
+    pub fn is_empty(&self) -> bool {
+        !self.polyline.iter().any(|&byte| byte & 0x80 == 0)
+    }
+}
+
+impl Iterator for BinaryPolylineIter64<'_> {
+    type Item = (f64, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (lat_change, lon_change) = bitwise_split64(self.varint_decode()?);
+        self.lat = self.lat.wrapping_add(zigzag64_decode(lat_change));
+        self.lon = self.lon.wrapping_add(zigzag64_decode(lon_change));
+        let lat = self.lat as f64 / self.scale;
+        let lon = self.lon as f64 / self.scale;
+        Some((lat, lon))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each point takes from 1 to 19 bytes.
+        let len = self.polyline.len();
+        (len / 19, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+/// 64-bit version of [`zigzag_decode`](crate::zigzag_decode).
+pub(crate) fn zigzag64_decode(i: u64) -> i64 {
+    (i >> 1) as i64 ^ -((i & 1) as i64)
+}
+
+/// 64-bit version of [`zigzag_encode`](crate::zigzag_encode).
+pub(crate) fn zigzag64_encode(value: i64) -> u64 {
+    (value << 1) as u64 ^ (value >> 63) as u64
+}
+
+/// 64-bit version of [`varint32_encode5`](crate::varint32_encode5).
+pub(crate) fn varint64_encode5(mut value: u64, buffer: &mut String) {
+    while value >= 0x20 {
+        let byte = char::from_u32((((value & 0x1F) | 0x20) + 63) as u32).unwrap();
+        buffer.push(byte);
+        value >>= 5;
+    }
+    let byte = char::from_u32((value + 63) as u32).unwrap();
+    buffer.push(byte);
+}
+
+/// 128-bit version of [`varint64_encode7`](crate::varint64_encode7).
+fn varint128_encode7(mut value: u128, buffer: &mut Vec<u8>) {
+    while value >= 0x80 {
+        let byte = (value & 0x7F) as u8 | 0x80;
+        buffer.push(byte);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+/// Merges bits from two 64-bit integers into a 128-bit integer the same way as [`bitwise_merge`].
+/// Low halves are merged into the low 64 bits of the result and high halves into the high 64 bits.
+fn bitwise_merge64(x: u64, y: u64) -> u128 {
+    let low = bitwise_merge(x as u32, y as u32);
+    let high = bitwise_merge((x >> 32) as u32, (y >> 32) as u32);
+    ((high as u128) << 64) | low as u128
+}
+
+/// Reverse operation to [`bitwise_merge64`], splitting a 128-bit integer into two 64-bit integers.
+fn bitwise_split64(v: u128) -> (u64, u64) {
+    let (x_low, y_low) = bitwise_split(v as u64);
+    let (x_high, y_high) = bitwise_split((v >> 64) as u64);
+    (
+        ((x_high as u64) << 32) | x_low as u64,
+        ((y_high as u64) << 32) | y_low as u64,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, decode_binary, encode, encode_binary};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn zigzag64() {
+        for value in [
+            0,
+            1,
+            -1,
+            2,
+            -2,
+            i32::MAX as i64,
+            i32::MIN as i64,
+            i64::MAX,
+            i64::MIN,
+        ] {
+            assert_eq!(zigzag64_decode(zigzag64_encode(value)), value);
+        }
+        for value in [0, 1, -1, 1000, -1000, i32::MAX, i32::MIN] {
+            assert_eq!(
+                zigzag64_encode(value as i64),
+                crate::zigzag_encode(value) as u64
+            );
+        }
+    }
+
+    #[test]
+    fn bitwise_merge64_test() {
+        assert_eq!(bitwise_merge64(0b1, 0b0), 0b01);
+        assert_eq!(bitwise_merge64(0b0, 0b1), 0b10);
+        assert_eq!(
+            bitwise_merge64(u64::MAX, 0),
+            0x5555_5555_5555_5555_5555_5555_5555_5555
+        );
+        assert_eq!(
+            bitwise_merge64(0, u64::MAX),
+            0xAAAA_AAAA_AAAA_AAAA_AAAA_AAAA_AAAA_AAAA
+        );
+        for (x, y) in [(0, 0), (1, 2), (u64::MAX, 0), (0x0123_4567_89AB_CDEF, 42)] {
+            assert_eq!(bitwise_split64(bitwise_merge64(x, y)), (x, y));
+            assert_eq!(
+                bitwise_merge64(x as u32 as u64, y as u32 as u64),
+                bitwise_merge(x as u32, y as u32) as u128
+            );
+        }
+    }
+
+    #[test]
+    fn compatible_with_32bit() {
+        let polyline = "angrIk~inAgwDybH_|D_{KeoEwtLozFo`Gre@tcA";
+        let points: Vec<_> = decode(5, polyline).collect();
+        assert_eq!(decode64(5, polyline).collect::<Vec<_>>(), points);
+        assert_eq!(encode64(5, points.iter().copied()), polyline);
+        assert_eq!(
+            encode_binary64(5, points.iter().copied()),
+            encode_binary(5, points.iter().copied())
+        );
+        let binary = encode_binary(5, points.iter().copied());
+        assert_eq!(decode_binary64(5, &binary).collect::<Vec<_>>(), points);
+        assert_eq!(
+            decode_binary(5, &binary).count(),
+            decode_binary64(5, &binary).count()
+        );
+    }
+
+    #[test]
+    fn high_precision() {
+        let points = [
+            (55.7121821178, 13.2156150923),
+            (-54.9065327139, -65.9920826437),
+            (89.9999999999, 179.9999999999),
+            (-90.0, -180.0),
+        ];
+        for precision in 8..=10 {
+            let polyline = encode64(precision, points);
+            let decoded: Vec<_> = decode64(precision, &polyline).collect();
+            let binary = encode_binary64(precision, points);
+            let decoded_binary: Vec<_> = decode_binary64(precision, &binary).collect();
+            assert_eq!(decoded, decoded_binary);
+
+            // Deltas are rounded independently, so the error may accumulate up to 0.5 per point.
+            let scale = 10.0_f64.powi(precision as i32);
+            let tolerance = points.len() as f64 * 0.5 / scale;
+            for (decoded, original) in decoded.iter().zip(points) {
+                assert!((decoded.0 - original.0).abs() <= tolerance);
+                assert!((decoded.1 - original.1).abs() <= tolerance);
+            }
+            assert_eq!(encode64(precision, decoded.iter().copied()), polyline);
+        }
+        assert_eq!(decode64(10, &encode64(10, points)).len(), 4);
+        assert_eq!(decode_binary64(10, &encode_binary64(10, points)).len(), 4);
+    }
+
+    #[test]
+    fn f64_precision_limit() {
+        // 13 decimal places of a geographic coordinate fit into f64
+        let point = (-39.0622049761219, 40.2519116139782);
+        assert_eq!(decode64(13, &encode64(13, [point])).next(), Some(point));
+
+        // 14 decimal places don't, the division by the scale is rounded to the nearest f64
+        let point = (0.0, 40.25191161397817);
+        assert_eq!(
+            decode64(14, &encode64(14, [point])).next(),
+            Some((0.0, 40.25191161397818))
+        );
+
+        // Small values still round-trip with the highest precision
+        let point = (0.1234567890123456, -0.6543210987654321);
+        assert_eq!(decode64(16, &encode64(16, [point])).next(), Some(point));
+    }
+
+    #[test]
+    fn broken_input() {
+        assert_eq!(decode64(10, "?").next(), None);
+        assert_eq!(decode64(10, "||||||||||||||||||||||||||||").next(), None);
+        // Bytes above 0x7F are neither terminators nor valid chunks, but don't panic
+        assert_eq!(decode64(5, "é").len(), 0);
+        assert!(decode64(5, "é").is_empty());
+        assert_eq!(decode_binary64(10, &[0x80; 32]).next(), None);
+        assert_eq!(encode(5, []), encode64(5, []));
+    }
+
+    #[test]
+    #[should_panic]
+    fn bad_precision() {
+        encode64(17, []);
+    }
+}
@@ -0,0 +1,247 @@
+//! Polylines with an arbitrary number of dimensions per point, e.g. XYZ, XYM or XYZM,
+//! compatible with OpenLayers' `ol/format/Polyline` with the corresponding stride.
+
+use crate::Scale;
+use crate::polyline64::{varint64_encode5, zigzag64_decode, zigzag64_encode};
+
+/// Iterator over `N`-dimensional points decoded from a polyline-encoded string.
+///
+/// Each dimension has its own [`Scale`] and is stored as a separate delta-encoded varint, so the first
+/// two dimensions with [`Scale::from_precision()`] are the same as latitude and longitude in [`decode()`](crate::decode).
+/// Values are accumulated in `i64`, which allows to store e.g. timestamps in milliseconds next to coordinates.
+///
+/// ```
+/// use polyline_iter::Scale;
+///
+/// // lat, lon, unix timestamp in seconds and speed in km/h with 1 decimal place
+/// let scales = [Scale::from_precision(5), Scale::from_precision(5), Scale::from_factor(1.0), Scale::from_factor(10.0)];
+/// let track = [
+///     [55.58513, 12.99958, 1_700_000_000.0, 42.5],
+///     [55.58600, 12.99900, 1_700_000_005.0, 40.0],
+/// ];
+/// let polyline = polyline_iter::encode_nd(scales, track);
+/// assert_eq!(polyline_iter::decode_nd(scales, &polyline).collect::<Vec<_>>(), track);
+/// ```
+pub struct PolylineIterNd<'a, const N: usize> {
+    polyline: &'a [u8],
+    scales: [f64; N],
+    /// Last processed values, multiplied by the corresponding scale.
+    values: [i64; N],
+}
+
+impl<'a, const N: usize> PolylineIterNd<'a, N> {
+    /// Creates a new iterator over `N`-dimensional points decoded from a polyline.
+    #[inline(always)]
+    pub fn new(scales: [Scale; N], polyline: &'a str) -> Self {
+        assert!(N > 0, "points must have at least one dimension");
+        PolylineIterNd {
+            polyline: polyline.as_bytes(),
+            scales: scales.map(Scale::factor),
+            values: [0; N],
+        }
+    }
+
+    #[inline(always)]
+    fn varint_decode(&mut self) -> Option<u64> {
+        let mut result = 0;
+        // 64 bits are stored in at most 13 chars by 5 bits each
+        for i in 0..self.polyline.len().min(13) {
+            // Casting here to i64 here to provide bad value instead of overflow panicking on bad input.
+            let chunk = (self.polyline[i] as i64) - 63;
+            result |= (chunk & 0x1f) << (i * 5); // no shift overflow as i < 13
+            if chunk & 0x20 == 0 {
+                self.polyline = &self.polyline[i + 1..];
+                return Some(result as u64);
+            }
+        }
+        None
+    }
+
+    fn varint_count(&self) -> usize {
+        self.polyline
+            .iter()
+            .filter(|&&byte| byte.wrapping_sub(63) & 0x20 == 0)
+            .count()
+    }
+
+    /// O(n) operation to count the number of points in the polyline without consuming the iterator.
+    pub fn len(&self) -> usize {
+        self.varint_count() / N
+    }
+
+    /// Checks if the polyline contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Strict O(n) check that the remaining polyline consists only of complete points,
+    /// i.e. the number of varints is a multiple of `N` and the last varint is not truncated.
+    pub fn is_complete(&self) -> bool {
+        let terminated = self
+            .polyline
+            .last()
+            .is_none_or(|&byte| byte.wrapping_sub(63) & 0x20 == 0);
+        terminated && self.varint_count() % N == 0
+    }
+}
+
+impl<const N: usize> Iterator for PolylineIterNd<'_, N> {
+    type Item = [f64; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut changes = [0; N];
+        for change in &mut changes {
+            *change = self.varint_decode()?;
+        }
+        let mut point = [0.0; N];
+        for i in 0..N {
+            // Wrapping to avoid overflow panics on malformed input
+            self.values[i] = self.values[i].wrapping_add(zigzag64_decode(changes[i]));
+            point[i] = self.values[i] as f64 / self.scales[i];
+        }
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each number takes from 1 to 13 chars.
+        let len = self.polyline.len() / N;
+        (len / 13, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+/// Decodes a polyline into an iterator over `N`-dimensional points, see [`PolylineIterNd`].
+///
+/// ```
+/// use polyline_iter::Scale;
+///
+/// // Stride 2 with precision 5 is the standard polyline
+/// let scales = [Scale::from_precision(5); 2];
+/// let points: Vec<_> = polyline_iter::decode_nd(scales, "angrIk~inAgwDybH").collect();
+/// assert_eq!(points, [[55.58513, 12.99958], [55.61461, 13.04627]]);
+/// ```
+#[inline(always)]
+pub fn decode_nd<const N: usize>(scales: [Scale; N], polyline: &str) -> PolylineIterNd<'_, N> {
+    PolylineIterNd::new(scales, polyline)
+}
+
+/// Encodes a sequence of `N`-dimensional points into a polyline string, multiplying each
+/// dimension by its own scale.
+///
+/// ```
+/// use polyline_iter::Scale;
+///
+/// let scales = [Scale::from_precision(5), Scale::from_precision(5), Scale::from_precision(2)];
+/// let points = [[55.58513, 12.99958, 17.5], [55.6, 13.0, 18.0]];
+/// // Same as `encode_3d()` as long as values fit into i32
+/// assert_eq!(polyline_iter::encode_nd(scales, points), polyline_iter::encode_3d(5, 2, points.map(|[x, y, z]| (x, y, z))));
+/// ```
+pub fn encode_nd<const N: usize>(
+    scales: [Scale; N],
+    points: impl IntoIterator<Item = [f64; N]>,
+) -> String {
+    assert!(N > 0, "points must have at least one dimension");
+    let scales = scales.map(Scale::factor);
+    let mut result = String::with_capacity(16);
+
+    let mut prev = [0; N];
+    for point in points {
+        for i in 0..N {
+            let value = (point[i] * scales[i]).round() as i64;
+            varint64_encode5(zigzag64_encode(value.wrapping_sub(prev[i])), &mut result);
+            prev[i] = value;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, decode_3d, encode};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn compatible_with_2d_and_3d() {
+        let polyline = "angrIk~inAgwDybH_|D_{KeoEwtLozFo`Gre@tcA";
+        let scales = [Scale::from_precision(5); 2];
+        let points: Vec<_> = decode(5, polyline).collect();
+        assert_eq!(
+            decode_nd(scales, polyline).collect::<Vec<_>>(),
+            points
+                .iter()
+                .map(|&(lat, lon)| [lat, lon])
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            encode_nd(scales, points.iter().map(|&(lat, lon)| [lat, lon])),
+            encode(5, points.iter().copied())
+        );
+
+        // The same polyline is a valid 1D polyline with twice as many points
+        let scales = [Scale::from_precision(5)];
+        assert_eq!(decode_nd(scales, polyline).count(), points.len() * 2);
+
+        let scales = [
+            Scale::from_precision(5),
+            Scale::from_precision(5),
+            Scale::from_precision(2),
+        ];
+        let polyline = "angrIk~inAklB}{AsAcB";
+        assert_eq!(
+            decode_nd(scales, polyline)
+                .map(|[lat, lon, elevation]| (lat, lon, elevation))
+                .collect::<Vec<_>>(),
+            decode_3d(5, 2, polyline).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn xyzm() {
+        let scales = [
+            Scale::from_precision(6),
+            Scale::from_precision(6),
+            Scale::from_precision(1),
+            Scale::from_factor(1000.0),
+        ];
+        // Timestamps in milliseconds don't fit into i32
+        let points = [
+            [55.585137, 12.999583, 12.5, 1_700_000_000.123],
+            [55.644854, 13.112187, 13.0, 1_700_000_001.5],
+            [55.678161, 13.182229, -2.1, 1_700_000_010.0],
+        ];
+        let polyline = encode_nd(scales, points);
+        let iter = decode_nd(scales, &polyline);
+        assert!(iter.is_complete());
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), points);
+
+        let truncated = &polyline[..polyline.len() - 1];
+        assert!(!decode_nd(scales, truncated).is_complete());
+        assert_eq!(decode_nd(scales, truncated).count(), 2);
+        assert_eq!(
+            decode_nd(scales, truncated).collect::<Vec<_>>(),
+            points[..2]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let scales = [Scale::from_precision(5); 4];
+        assert_eq!(encode_nd(scales, []), "");
+        assert!(decode_nd(scales, "").is_empty());
+        assert!(decode_nd(scales, "").is_complete());
+        assert_eq!(decode_nd(scales, "").next(), None);
+    }
+
+    #[test]
+    fn non_ascii() {
+        // Bytes above 0x7F are neither terminators nor valid chunks, but don't panic
+        let scales = [Scale::from_precision(5); 2];
+        assert_eq!(decode_nd(scales, "é").len(), 0);
+        assert!(!decode_nd(scales, "é").is_complete());
+    }
+}
@@ -0,0 +1,44 @@
+/// Multiplier applied to coordinates before they are rounded to integers and encoded.
+///
+/// The standard polyline formats use a power of ten, e.g. `1e5` for polyline5 and `1e6` for polyline6,
+/// but any positive finite factor works, for example `2^24` fixed-point, `3600` for arc-seconds
+/// or `100` to store projected meters at centimeter resolution.
+///
+/// Scaled coordinates and the changes between them must fit into `i32`.
+///
+/// ```
+/// use polyline_iter::Scale;
+///
+/// assert_eq!(Scale::from_precision(5), Scale::from_factor(100_000.0));
+/// assert_eq!(Scale::from_precision(6).factor(), 1e6);
+///
+/// // Arc-seconds
+/// let polyline = polyline_iter::encode_scaled(Scale::from_factor(3600.0), [(55.5, 13.25)]);
+/// assert_eq!(polyline, polyline_iter::encode(0, [(55.5 * 3600.0, 13.25 * 3600.0)]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Scale(f64);
+
+impl Scale {
+    /// Scale for the given number of decimal places, i.e. `10^precision`.
+    #[inline(always)]
+    pub fn from_precision(precision: u8) -> Self {
+        Scale(10.0_f64.powi(precision as i32))
+    }
+
+    /// Scale with an arbitrary positive finite factor.
+    #[inline(always)]
+    pub fn from_factor(factor: f64) -> Self {
+        assert!(
+            factor.is_finite() && factor > 0.0,
+            "scale factor must be positive and finite"
+        );
+        Scale(factor)
+    }
+
+    /// Returns the factor coordinates are multiplied by.
+    #[inline(always)]
+    pub fn factor(self) -> f64 {
+        self.0
+    }
+}
@@ -0,0 +1,372 @@
+//! Adapters for `#[serde(with = "...")]` that (de)serialize points as polyline strings.
+//!
+//! - [`polyline5`] and [`polyline6`] use the text format, see [`encode()`](crate::encode).
+//! - [`binary`] uses the binary format with precision 6, see [`encode_binary()`](crate::encode_binary).
+//!   Human-readable formats, like JSON, get it as an unpadded base64url string,
+//!   see [`encode_binary_base64url()`](crate::encode_binary_base64url), and the others as raw bytes.
+//!
+//! Malformed input is rejected with the error from [`try_decode()`](crate::try_decode)
+//! and [`try_decode_binary()`](crate::try_decode_binary) instead of being silently truncated.
+//!
+//! Available with the `serde` feature.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Route {
+//!     #[serde(with = "polyline_iter::serde::polyline5")]
+//!     shape: Vec<(f64, f64)>,
+//!     #[serde(with = "polyline_iter::serde::binary")]
+//!     detailed_shape: Box<[(f64, f64)]>,
+//! }
+//!
+//! let route = Route {
+//!     shape: vec![(55.58513, 12.99958), (55.61461, 13.04627)],
+//!     detailed_shape: Box::new([(55.585137, 12.999583)]),
+//! };
+//! let json = serde_json::to_string(&route).unwrap();
+//! assert_eq!(json, r#"{"shape":"angrIk~inAgwDybH","detailed_shape":"rL252YmUsQs"}"#);
+//! assert_eq!(serde_json::from_str::<Route>(&json).unwrap(), route);
+//! ```
+
+use std::fmt;
+
+use ::serde::de::{Deserializer, Error, Visitor};
+use ::serde::ser::Serializer;
+
+use crate::{Scale, scale_point, varint32_encode5, zigzag_encode};
+
+/// Containers of points supported by the adapters.
+///
+/// Implemented for `Vec<(f64, f64)>` and `Box<[(f64, f64)]>`, and for `Vec<geo_types::Coord>`
+/// and `geo_types::LineString` with the `geo-types` feature. The crate has no owned polyline type
+/// to implement it for, as decoded polylines are iterators borrowing the string, like [`PolylineIter`](crate::PolylineIter).
+/// Other containers can implement it as well.
+pub trait Points: Sized {
+    /// Iterates over `(lat, lon)` pairs of the points.
+    fn points(&self) -> impl Iterator<Item = (f64, f64)> + '_;
+
+    /// Collects decoded `(lat, lon)` pairs into the container.
+    fn from_points(points: impl Iterator<Item = (f64, f64)>) -> Self;
+}
+
+impl Points for Vec<(f64, f64)> {
+    fn points(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.iter().copied()
+    }
+
+    fn from_points(points: impl Iterator<Item = (f64, f64)>) -> Self {
+        points.collect()
+    }
+}
+
+impl Points for Box<[(f64, f64)]> {
+    fn points(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.iter().copied()
+    }
+
+    fn from_points(points: impl Iterator<Item = (f64, f64)>) -> Self {
+        points.collect()
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl Points for Vec<geo_types::Coord> {
+    fn points(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.iter().copied().map(crate::geo::from_coord)
+    }
+
+    fn from_points(points: impl Iterator<Item = (f64, f64)>) -> Self {
+        points.map(crate::geo::to_coord).collect()
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl Points for geo_types::LineString {
+    fn points(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.coords().copied().map(crate::geo::from_coord)
+    }
+
+    fn from_points(points: impl Iterator<Item = (f64, f64)>) -> Self {
+        points.map(crate::geo::to_coord).collect()
+    }
+}
+
+/// Serializes points as polyline5 string.
+pub mod polyline5 {
+    use super::*;
+
+    pub fn serialize<T: Points, S: Serializer>(
+        points: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_text(5, points, serializer)
+    }
+
+    pub fn deserialize<'de, T: Points, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        deserializer.deserialize_str(TextVisitor::new(5))
+    }
+}
+
+/// Serializes points as polyline6 string.
+pub mod polyline6 {
+    use super::*;
+
+    pub fn serialize<T: Points, S: Serializer>(
+        points: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_text(6, points, serializer)
+    }
+
+    pub fn deserialize<'de, T: Points, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        deserializer.deserialize_str(TextVisitor::new(6))
+    }
+}
+
+/// Serializes points in the binary format with precision 6, as base64url string for human-readable formats.
+pub mod binary {
+    use super::*;
+
+    pub fn serialize<T: Points, S: Serializer>(
+        points: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&crate::encode_binary_base64url(6, points.points()))
+        } else {
+            serializer.serialize_bytes(&crate::encode_binary(6, points.points()))
+        }
+    }
+
+    pub fn deserialize<'de, T: Points, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BinaryVisitor(std::marker::PhantomData))
+        } else {
+            deserializer.deserialize_bytes(BinaryVisitor(std::marker::PhantomData))
+        }
+    }
+}
+
+fn serialize_text<T: Points, S: Serializer>(
+    precision: u8,
+    points: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    // Serializers like `serde_json` write `Display` directly into the output, without an intermediate `String`
+    serializer.collect_str(&TextPolyline { precision, points })
+}
+
+/// Writes points as a polyline string by [`fmt::Display`].
+struct TextPolyline<'a, T> {
+    precision: u8,
+    points: &'a T,
+}
+
+impl<T: Points> fmt::Display for TextPolyline<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scale = Scale::from_precision(self.precision).factor();
+        // Two numbers take at most 14 chars, so the buffer is never reallocated
+        let mut buffer = String::with_capacity(14);
+        let mut prev = (0, 0);
+        for point in self.points.points() {
+            // Changes are computed exactly as in `encode()` to produce the same string
+            let point = scale_point(point, scale);
+            let lat_change = point.0.wrapping_sub(prev.0);
+            let lon_change = point.1.wrapping_sub(prev.1);
+            buffer.clear();
+            varint32_encode5(zigzag_encode(lat_change), &mut buffer);
+            varint32_encode5(zigzag_encode(lon_change), &mut buffer);
+            f.write_str(&buffer)?;
+            prev = point;
+        }
+        Ok(())
+    }
+}
+
+struct TextVisitor<T> {
+    precision: u8,
+    points: std::marker::PhantomData<T>,
+}
+
+impl<T> TextVisitor<T> {
+    fn new(precision: u8) -> Self {
+        TextVisitor {
+            precision,
+            points: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: Points> Visitor<'_> for TextVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a polyline{} string", self.precision)
+    }
+
+    fn visit_str<E: Error>(self, polyline: &str) -> Result<T, E> {
+        let iter = crate::try_decode(self.precision, polyline).map_err(E::custom)?;
+        Ok(T::from_points(iter))
+    }
+}
+
+struct BinaryVisitor<T>(std::marker::PhantomData<T>);
+
+impl<T: Points> Visitor<'_> for BinaryVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a binary polyline as bytes or base64url string")
+    }
+
+    fn visit_bytes<E: Error>(self, polyline: &[u8]) -> Result<T, E> {
+        let iter = crate::try_decode_binary(6, polyline).map_err(E::custom)?;
+        Ok(T::from_points(iter))
+    }
+
+    fn visit_str<E: Error>(self, polyline: &str) -> Result<T, E> {
+        let bytes = crate::base64url::base64url_decode(polyline)
+            .ok_or_else(|| E::custom("invalid base64url string"))?;
+        self.visit_bytes(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::serde::de::IntoDeserializer;
+    use ::serde::de::value::{BytesDeserializer, Error as ValueError};
+    use ::serde::{Deserialize, Serialize};
+    use pretty_assertions::assert_eq;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Shapes {
+        #[serde(with = "polyline5")]
+        polyline5: Vec<(f64, f64)>,
+        #[serde(with = "polyline6")]
+        polyline6: Box<[(f64, f64)]>,
+        #[serde(with = "binary")]
+        binary: Vec<(f64, f64)>,
+    }
+
+    #[test]
+    fn round_trip() {
+        // `(-0.00015, 0.0)` is encoded as `\?`, which is escaped in JSON,
+        // and the change between the last points isn't a multiple of the scale
+        let points = vec![
+            (-0.00015, 0.0),
+            (90.0, -180.0),
+            (-90.0, 180.0),
+            (-89.9999996, 179.9999994),
+        ];
+        let shapes = Shapes {
+            polyline5: points.clone(),
+            polyline6: points.clone().into_boxed_slice(),
+            binary: points.clone(),
+        };
+        let json = serde_json::to_string(&shapes).unwrap();
+        assert!(json.starts_with(r#"{"polyline5":"\\?"#), "{json}");
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["polyline5"], crate::encode(5, points.iter().copied()));
+        assert_eq!(value["polyline6"], crate::encode(6, points.iter().copied()));
+        assert_eq!(
+            value["binary"],
+            crate::encode_binary_base64url(6, points.iter().copied())
+        );
+
+        let decoded = serde_json::from_str::<Shapes>(&json).unwrap();
+        assert_eq!(
+            decoded.polyline5,
+            crate::decode(5, value["polyline5"].as_str().unwrap()).collect::<Vec<_>>()
+        );
+        assert_eq!(decoded.polyline5[3], (-90.0, 180.0));
+        assert_eq!(decoded.polyline6[3], (-90.0, 179.999999));
+        assert_eq!(&*decoded.polyline6, decoded.binary);
+
+        let empty = Shapes {
+            polyline5: Vec::new(),
+            polyline6: Box::new([]),
+            binary: Vec::new(),
+        };
+        let json = serde_json::to_string(&empty).unwrap();
+        assert_eq!(json, r#"{"polyline5":"","polyline6":"","binary":""}"#);
+        assert_eq!(serde_json::from_str::<Shapes>(&json).unwrap(), empty);
+    }
+
+    #[cfg(feature = "geo-types")]
+    #[test]
+    fn geo_types() {
+        use geo_types::line_string;
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Shapes {
+            #[serde(with = "polyline5")]
+            coords: Vec<geo_types::Coord>,
+            #[serde(with = "binary")]
+            line: geo_types::LineString,
+        }
+
+        let line = line_string![(x: 12.99958, y: 55.58513), (x: 13.04627, y: 55.61461)];
+        let shapes = Shapes {
+            coords: line.0.clone(),
+            line,
+        };
+        let json = serde_json::to_value(&shapes).unwrap();
+        assert_eq!(json["coords"], "angrIk~inAgwDybH");
+        assert_eq!(serde_json::from_value::<Shapes>(json).unwrap(), shapes);
+    }
+
+    #[test]
+    fn bytes() {
+        let points = vec![(55.585137, 12.999583), (-90.0, 180.0), (90.0, -180.0)];
+        let binary = crate::encode_binary(6, points.iter().copied());
+        let deserializer = BytesDeserializer::<ValueError>::new(&binary);
+        assert_eq!(
+            binary::deserialize::<Vec<(f64, f64)>, _>(deserializer).unwrap(),
+            points
+        );
+
+        let deserializer = BytesDeserializer::<ValueError>::new(&binary[..binary.len() - 1]);
+        assert!(binary::deserialize::<Vec<(f64, f64)>, _>(deserializer).is_err());
+    }
+
+    #[test]
+    fn malformed() {
+        let error = serde_json::from_str::<Shapes>(
+            r#"{"polyline5":"angrIk~inAgwD","polyline6":"","binary":""}"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "can't decode number at offset 13: unexpected end of input at line 1 column 28"
+        );
+        let error = serde_json::from_str::<Shapes>(
+            r#"{"polyline5":"","polyline6":"","binary":"wNrv4rc="}"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid base64url string at line 1 column 50"
+        );
+        let error =
+            serde_json::from_str::<Shapes>(r#"{"polyline5":[],"polyline6":"","binary":""}"#)
+                .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid type: sequence, expected a polyline5 string at line 1 column 13"
+        );
+
+        let deserializer: ::serde::de::value::StrDeserializer<ValueError> =
+            "angrIk~inA gwDybH".into_deserializer();
+        assert!(polyline5::deserialize::<Vec<(f64, f64)>, _>(deserializer).is_err());
+    }
+}
@@ -0,0 +1,316 @@
+//! Conversion between polyline formats and precisions without decoding points into floats.
+
+use crate::{BinaryPolylineIter, EncodeError, EncodeErrorKind, PolylineIter};
+use crate::{bitwise_merge, bitwise_split, varint32_encode5, varint64_encode7};
+use crate::{zigzag_decode, zigzag_encode};
+
+/// Converts a text polyline into the format of [`encode_binary()`](crate::encode_binary).
+///
+/// Works directly on the zigzag-encoded changes, so the result is exact and doesn't depend on the precision:
+/// it is the same as `encode_binary(p, decode(p, polyline))` for any precision `p`, but faster and without
+/// float rounding. Conversion stops at the first malformed point.
+///
+/// ```
+/// let polyline = "angrIk~inAgwDybH_|D_{K";
+/// let binary = polyline_iter::text_to_binary(polyline);
+/// assert_eq!(binary, polyline_iter::encode_binary(5, polyline_iter::decode(5, polyline)));
+/// assert_eq!(polyline_iter::binary_to_text(&binary), polyline);
+/// ```
+pub fn text_to_binary(polyline: &str) -> Vec<u8> {
+    // Precision is irrelevant, as only the raw changes are read
+    let mut iter = PolylineIter::new(0, polyline);
+    let mut result = Vec::with_capacity(polyline.len());
+    while let (Some(lat_change), Some(lon_change)) = (iter.varint_decode(), iter.varint_decode()) {
+        varint64_encode7(bitwise_merge(lat_change, lon_change), &mut result);
+    }
+    result
+}
+
+/// Converts binary data created by [`encode_binary()`](crate::encode_binary) into a text polyline,
+/// the reverse of [`text_to_binary()`]. Conversion stops at the first malformed point.
+///
+/// ```
+/// let points = [(55.58513, 12.99958), (55.61461, 13.04627)];
+/// let binary = polyline_iter::encode_binary(5, points);
+/// assert_eq!(polyline_iter::binary_to_text(&binary), polyline_iter::encode(5, points));
+/// ```
+pub fn binary_to_text(binary: &[u8]) -> String {
+    let mut iter = BinaryPolylineIter::new(0, binary);
+    // Each byte holds 7 bits, and each char holds 5 bits
+    let mut result = String::with_capacity(binary.len() * 7 / 5 + 2);
+    while let Some(value) = iter.varint_decode() {
+        let (lat_change, lon_change) = bitwise_split(value);
+        varint32_encode5(lat_change, &mut result);
+        varint32_encode5(lon_change, &mut result);
+    }
+    result
+}
+
+/// Converts a text polyline from one precision to another, e.g. polyline6 into polyline5.
+///
+/// Same as `encode(to, decode(from, polyline))`, but every coordinate is rescaled as integer and rounded
+/// half away from zero exactly, while going through floats may round ties the other way.
+/// Increasing the precision is lossless, but fails with [`EncodeErrorKind::DeltaOverflow`] if a coordinate
+/// or the change between points doesn't fit into `i32` anymore, e.g. when crossing the antimeridian with precision 7.
+/// Conversion stops at the first malformed point.
+///
+/// ```
+/// let polyline5 = polyline_iter::transcode(6, 5, "avs_iB}xlxWissBw|zEu``AsxgCyoaAm_z@").unwrap();
+/// assert_eq!(
+///     polyline_iter::decode(5, &polyline5).collect::<Vec<_>>(),
+///     [(55.58514, 12.99958), (55.64485, 13.11219), (55.67816, 13.18223), (55.71222, 13.21244)]
+/// );
+///
+/// // Through floats, 0.000035 * 10^5 is 3.4999999999999996 and rounds down
+/// let polyline6 = polyline_iter::encode(6, [(0.000035, 0.0)]);
+/// let via_floats = polyline_iter::encode(5, polyline_iter::decode(6, &polyline6));
+/// assert_eq!(polyline_iter::decode(5, &via_floats).next(), Some((0.00003, 0.0)));
+/// let exact = polyline_iter::transcode(6, 5, &polyline6).unwrap();
+/// assert_eq!(polyline_iter::decode(5, &exact).next(), Some((0.00004, 0.0)));
+/// ```
+pub fn transcode(
+    from_precision: u8,
+    to_precision: u8,
+    polyline: &str,
+) -> Result<String, EncodeError> {
+    let mut iter = PolylineIter::new(from_precision, polyline);
+    let changes = std::iter::from_fn(|| Some((iter.varint_decode()?, iter.varint_decode()?)));
+    let mut result = String::with_capacity(polyline.len());
+    rescale(
+        from_precision,
+        to_precision,
+        changes,
+        |lat_change, lon_change| {
+            varint32_encode5(lat_change, &mut result);
+            varint32_encode5(lon_change, &mut result);
+        },
+    )?;
+    Ok(result)
+}
+
+/// Binary version of [`transcode()`], for data created by [`encode_binary()`](crate::encode_binary).
+///
+/// ```
+/// let points = [(55.585137, 12.999583), (55.644854, 13.112187)];
+/// let binary6 = polyline_iter::encode_binary(6, points);
+/// let binary5 = polyline_iter::transcode_binary(6, 5, &binary6).unwrap();
+/// assert_eq!(
+///     polyline_iter::decode_binary(5, &binary5).collect::<Vec<_>>(),
+///     [(55.58514, 12.99958), (55.64485, 13.11219)]
+/// );
+/// ```
+pub fn transcode_binary(
+    from_precision: u8,
+    to_precision: u8,
+    binary: &[u8],
+) -> Result<Vec<u8>, EncodeError> {
+    let mut iter = BinaryPolylineIter::new(from_precision, binary);
+    let changes = std::iter::from_fn(|| iter.varint_decode().map(bitwise_split));
+    let mut result = Vec::with_capacity(binary.len());
+    rescale(
+        from_precision,
+        to_precision,
+        changes,
+        |lat_change, lon_change| {
+            varint64_encode7(bitwise_merge(lat_change, lon_change), &mut result);
+        },
+    )?;
+    Ok(result)
+}
+
+/// Restores absolute coordinates from zigzag-encoded changes, rescales them and passes
+/// the new zigzag-encoded changes to `write`.
+fn rescale(
+    from_precision: u8,
+    to_precision: u8,
+    changes: impl Iterator<Item = (u32, u32)>,
+    mut write: impl FnMut(u32, u32),
+) -> Result<(), EncodeError> {
+    assert!(to_precision <= 7, "i32 can hold up to 180 * 10^7");
+    let rescale_value = |value: i32| -> Option<i32> {
+        if to_precision >= from_precision {
+            return value.checked_mul(10i32.pow((to_precision - from_precision) as u32));
+        }
+        let divisor = 10i32.pow((from_precision - to_precision) as u32);
+        let (quotient, remainder) = (value / divisor, value % divisor);
+        // Half away from zero, as `f64::round()` used by the encoders
+        if remainder.unsigned_abs() * 2 >= divisor as u32 {
+            Some(quotient + value.signum())
+        } else {
+            Some(quotient)
+        }
+    };
+
+    let (mut lat, mut lon) = (0i32, 0i32);
+    let (mut new_lat, mut new_lon) = (0i32, 0i32);
+    for (index, (lat_change, lon_change)) in changes.enumerate() {
+        let error = |kind| EncodeError { index, kind };
+        // Wrapping to avoid overflow panics on malformed input
+        lat = lat.wrapping_add(zigzag_decode(lat_change));
+        lon = lon.wrapping_add(zigzag_decode(lon_change));
+
+        let (Some(next_lat), Some(next_lon)) = (rescale_value(lat), rescale_value(lon)) else {
+            return Err(error(EncodeErrorKind::DeltaOverflow));
+        };
+        let (Some(lat_change), Some(lon_change)) =
+            (next_lat.checked_sub(new_lat), next_lon.checked_sub(new_lon))
+        else {
+            return Err(error(EncodeErrorKind::DeltaOverflow));
+        };
+        write(zigzag_encode(lat_change), zigzag_encode(lon_change));
+        (new_lat, new_lon) = (next_lat, next_lon);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::{LONG_POLYLINE6, MEDIUM_POLYLINE6};
+    use crate::{decode, decode_binary, encode, encode_binary};
+    use pretty_assertions::assert_eq;
+
+    /// Rounds `value / 10^shift` half away from zero, which is exact for floats, as every tie is representable.
+    fn quantize(value: i32, shift: u8) -> i32 {
+        (value as f64 / 10f64.powi(shift as i32)).round() as i32
+    }
+
+    #[test]
+    fn same_as_float_conversion() {
+        for polyline in [MEDIUM_POLYLINE6, LONG_POLYLINE6] {
+            let binary = text_to_binary(polyline);
+            assert_eq!(binary, encode_binary(6, decode(6, polyline)));
+            assert_eq!(binary_to_text(&binary), polyline);
+            // Precision doesn't matter
+            assert_eq!(
+                decode_binary(5, &binary).collect::<Vec<_>>(),
+                decode(5, polyline).collect::<Vec<_>>()
+            );
+        }
+
+        assert_eq!(text_to_binary(""), b"");
+        assert_eq!(binary_to_text(b""), "");
+    }
+
+    #[test]
+    fn extremes() {
+        let points = [
+            (214.7483647, -214.7483648),
+            (0.0, -0.0000001),
+            (-214.7483648, 214.7483647),
+        ];
+        let polyline = encode(7, points);
+        let binary = encode_binary(7, points);
+        assert_eq!(text_to_binary(&polyline), binary);
+        assert_eq!(binary_to_text(&binary), polyline);
+    }
+
+    #[test]
+    fn malformed_tail() {
+        // The last point misses its longitude
+        assert_eq!(
+            binary_to_text(&text_to_binary("angrIk~inAgwDybH_|D")),
+            "angrIk~inAgwDybH"
+        );
+        // The last varint is not terminated
+        let binary = encode_binary(5, [(55.58513, 12.99958), (55.61461, 13.04627)]);
+        assert_eq!(binary_to_text(&binary[..binary.len() - 1]), "angrIk~inA");
+    }
+
+    #[test]
+    fn transcode_matches_direct_quantization() {
+        for polyline in [MEDIUM_POLYLINE6, LONG_POLYLINE6] {
+            // Original high-precision data as integers
+            let original: Vec<_> = decode(6, polyline)
+                .map(|(lat, lon)| ((lat * 1e6).round() as i32, (lon * 1e6).round() as i32))
+                .collect();
+            for to_precision in 0..=5 {
+                let shift = 6 - to_precision;
+                let expected: Vec<_> = original
+                    .iter()
+                    .map(|&(lat, lon)| (quantize(lat, shift), quantize(lon, shift)))
+                    .collect();
+
+                let transcoded = transcode(6, to_precision, polyline).unwrap();
+                let decoded: Vec<_> = decode(0, &transcoded)
+                    .map(|(lat, lon)| (lat as i32, lon as i32))
+                    .collect();
+                assert_eq!(decoded, expected);
+                assert_eq!(
+                    transcode_binary(6, to_precision, &text_to_binary(polyline)).unwrap(),
+                    text_to_binary(&transcoded)
+                );
+            }
+
+            // Increasing precision is lossless
+            let polyline7 = transcode(6, 7, polyline).unwrap();
+            assert_eq!(transcode(7, 6, &polyline7).unwrap(), polyline);
+            assert_eq!(transcode(6, 6, polyline).unwrap(), polyline);
+        }
+    }
+
+    #[test]
+    fn transcode_rounding() {
+        let polyline6 = encode(
+            6,
+            [
+                (0.000005, -0.000005),
+                (0.000015, -0.000015),
+                (0.000014, -0.000016),
+                (0.0, 0.0),
+            ],
+        );
+        assert_eq!(
+            decode(5, &transcode(6, 5, &polyline6).unwrap()).collect::<Vec<_>>(),
+            [
+                (0.00001, -0.00001),
+                (0.00002, -0.00002),
+                (0.00001, -0.00002),
+                (0.0, 0.0)
+            ]
+        );
+
+        // Transcoding directly rounds once, and 0.0000049 is closer to 0.00000 than to 0.00001,
+        // while going through precision 6 rounds twice, to 0.000005 and then to 0.00001
+        let polyline7 = encode(7, [(0.0000049, -0.0000049)]);
+        assert_eq!(
+            decode(5, &transcode(7, 5, &polyline7).unwrap()).collect::<Vec<_>>(),
+            [(0.0, 0.0)]
+        );
+        let polyline6 = transcode(7, 6, &polyline7).unwrap();
+        assert_eq!(
+            decode(5, &transcode(6, 5, &polyline6).unwrap()).collect::<Vec<_>>(),
+            [(0.00001, -0.00001)]
+        );
+    }
+
+    #[test]
+    fn transcode_overflow() {
+        let polyline = encode(5, [(90.0, -180.0), (-90.0, 180.0)]);
+        assert_eq!(
+            transcode(5, 7, &polyline),
+            Err(EncodeError {
+                index: 1,
+                kind: EncodeErrorKind::DeltaOverflow
+            })
+        );
+        // The coordinate itself doesn't fit
+        let polyline = encode(5, [(45.0, 90.0), (-45.0, 220.0)]);
+        assert_eq!(
+            transcode(5, 7, &polyline),
+            Err(EncodeError {
+                index: 1,
+                kind: EncodeErrorKind::DeltaOverflow
+            })
+        );
+        let binary = encode_binary(5, [(90.0, -180.0), (-90.0, 180.0)]);
+        assert_eq!(
+            transcode_binary(5, 7, &binary).map_err(|error| error.kind),
+            Err(EncodeErrorKind::DeltaOverflow)
+        );
+        assert_eq!(
+            transcode_binary(5, 6, &binary).map(|binary| decode_binary(6, &binary).collect()),
+            Ok(vec![(90.0, -180.0), (-90.0, 180.0)])
+        );
+    }
+}
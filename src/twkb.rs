@@ -0,0 +1,603 @@
+//! [TWKB (Tiny Well-Known Binary)](https://github.com/TWKB/Specification/blob/master/twkb.md) for LineString,
+//! MultiLineString and Polygon geometries, as produced by PostGIS `ST_AsTWKB`.
+//!
+//! TWKB uses the same zigzag and 7-bit varint delta encoding as [`encode_binary()`](crate::encode_binary),
+//! but stores x and y as separate varints and adds a header with the geometry type and precision.
+//! TWKB coordinates are `(x, y)`, which this module maps to `(lat, lon) = (y, x)` to match the rest of the crate.
+//!
+//! ```
+//! use polyline_iter::twkb;
+//!
+//! // SELECT ST_AsTWKB('LINESTRING(1 1, 5 5)'::geometry)
+//! let data = [0x02, 0x00, 0x02, 0x02, 0x02, 0x08, 0x08];
+//! let points: Vec<_> = twkb::decode(&data).unwrap().collect();
+//! assert_eq!(points, [(1.0, 1.0), (5.0, 5.0)]);
+//! assert_eq!(twkb::encode_linestring(0, points, twkb::EncodeOptions::default()), data);
+//! ```
+
+use crate::polyline64::{zigzag64_decode, zigzag64_encode};
+use crate::{varint64_decode7, varint64_encode7};
+
+const HAS_BBOX: u8 = 0x01;
+const HAS_SIZE: u8 = 0x02;
+const HAS_ID_LIST: u8 = 0x04;
+const HAS_EXTENDED_DIMS: u8 = 0x08;
+const IS_EMPTY: u8 = 0x10;
+
+/// Geometry types supported by this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeometryType {
+    Point = 1,
+    LineString = 2,
+    Polygon = 3,
+    MultiPoint = 4,
+    MultiLineString = 5,
+}
+
+/// Bounding box stored in the TWKB header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_lat: f64,
+    pub min_lon: f64,
+    pub max_lat: f64,
+    pub max_lon: f64,
+}
+
+/// Metadata stored at the beginning of every TWKB geometry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Header {
+    pub geometry_type: GeometryType,
+    /// Number of decimal places of x and y, from -8 to 7.
+    pub precision: i8,
+    /// Whether points have Z values, which are skipped by the decoder.
+    pub has_z: bool,
+    /// Whether points have M values, which are skipped by the decoder.
+    pub has_m: bool,
+    /// Size of the geometry in bytes after the size field, if present.
+    pub size: Option<u64>,
+    pub bbox: Option<BoundingBox>,
+}
+
+/// Error returned by [`decode()`] when the header is malformed or unsupported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The data ends in the middle of the header.
+    Truncated,
+    /// Geometry type is not supported, e.g. MultiPolygon or GeometryCollection.
+    UnsupportedType(u8),
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::Truncated => write!(f, "truncated TWKB header"),
+            DecodeError::UnsupportedType(geometry_type) => {
+                write!(f, "unsupported TWKB geometry type {geometry_type}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Iterator over points decoded from TWKB data, created by [`decode()`].
+///
+/// Iterates over points of all parts (lines of MultiLineString or rings of Polygon) in order.
+/// Use [`TwkbIter::next_part()`] to iterate over parts separately.
+/// Just like [`BinaryPolylineIter`](crate::BinaryPolylineIter), stops on the first malformed point.
+///
+/// ```
+/// use polyline_iter::twkb;
+///
+/// let lines = [vec![(55.58513, 12.99958), (55.61461, 13.04627)], vec![(55.64485, 13.11219)]];
+/// let data = twkb::encode_multilinestring(5, lines.clone(), twkb::EncodeOptions::default());
+///
+/// let mut iter = twkb::decode(&data).unwrap();
+/// assert_eq!(iter.next_part().unwrap().collect::<Vec<_>>(), lines[0]);
+/// assert_eq!(iter.next_part().unwrap().collect::<Vec<_>>(), lines[1]);
+/// assert!(iter.next_part().is_none());
+/// ```
+pub struct TwkbIter<'a> {
+    data: &'a [u8],
+    header: Header,
+    scale: f64,
+    /// Number of values per point, from 2 to 4.
+    dimensions: usize,
+    /// Multi geometries may have ids of their members before them, which are skipped.
+    has_id_list: bool,
+    /// Parts after the current one.
+    parts_left: u64,
+    /// Points left in the current part.
+    points_left: u64,
+    /// Last processed x and y, multiplied by the scale. Z and M are not used, but still accumulated.
+    last: [i64; 4],
+}
+
+impl<'a> TwkbIter<'a> {
+    /// Parses the header and creates a new iterator over the points that follow it.
+    pub fn new(mut data: &'a [u8]) -> Result<Self, DecodeError> {
+        let &[type_and_precision, metadata, ..] = data else {
+            return Err(DecodeError::Truncated);
+        };
+        data = &data[2..];
+        let geometry_type = match type_and_precision & 0x0F {
+            1 => GeometryType::Point,
+            2 => GeometryType::LineString,
+            3 => GeometryType::Polygon,
+            4 => GeometryType::MultiPoint,
+            5 => GeometryType::MultiLineString,
+            other => return Err(DecodeError::UnsupportedType(other)),
+        };
+        let precision = zigzag64_decode((type_and_precision >> 4) as u64) as i8;
+        let scale = 10.0_f64.powi(precision as i32);
+
+        let (mut has_z, mut has_m) = (false, false);
+        if metadata & HAS_EXTENDED_DIMS != 0 {
+            let (&extended, rest) = data.split_first().ok_or(DecodeError::Truncated)?;
+            data = rest;
+            has_z = extended & 0x01 != 0;
+            has_m = extended & 0x02 != 0;
+        }
+        let dimensions = 2 + has_z as usize + has_m as usize;
+
+        let size = if metadata & HAS_SIZE != 0 {
+            Some(varint64_decode7(&mut data).ok_or(DecodeError::Truncated)?)
+        } else {
+            None
+        };
+
+        let bbox = if metadata & HAS_BBOX != 0 {
+            let mut values = [0; 8];
+            for value in &mut values[..dimensions * 2] {
+                *value =
+                    zigzag64_decode(varint64_decode7(&mut data).ok_or(DecodeError::Truncated)?);
+            }
+            let [min_x, delta_x, min_y, delta_y, ..] = values;
+            Some(BoundingBox {
+                min_lat: min_y as f64 / scale,
+                min_lon: min_x as f64 / scale,
+                max_lat: min_y.wrapping_add(delta_y) as f64 / scale,
+                max_lon: min_x.wrapping_add(delta_x) as f64 / scale,
+            })
+        } else {
+            None
+        };
+
+        let mut iter = TwkbIter {
+            data,
+            header: Header {
+                geometry_type,
+                precision,
+                has_z,
+                has_m,
+                size,
+                bbox,
+            },
+            scale,
+            dimensions,
+            has_id_list: metadata & HAS_ID_LIST != 0,
+            parts_left: 1,
+            points_left: 0,
+            last: [0; 4],
+        };
+        if metadata & IS_EMPTY != 0 {
+            iter.parts_left = 0;
+        } else if matches!(
+            geometry_type,
+            GeometryType::Polygon | GeometryType::MultiLineString
+        ) {
+            iter.parts_left = varint64_decode7(&mut iter.data).unwrap_or(0);
+            if geometry_type == GeometryType::MultiLineString && iter.has_id_list {
+                iter.skip_varints(iter.parts_left);
+            }
+        }
+        Ok(iter)
+    }
+
+    /// Returns the metadata from the TWKB header.
+    pub fn header(&self) -> Header {
+        self.header
+    }
+
+    /// Starts the next part, i.e. the next line of MultiLineString or the next ring of Polygon.
+    /// Points left in the current part are skipped.
+    pub fn next_part(&mut self) -> Option<Part<'_, 'a>> {
+        while self.points_left > 0 {
+            self.next_point()?;
+        }
+        self.start_part()?;
+        Some(Part { iter: self })
+    }
+
+    fn start_part(&mut self) -> Option<()> {
+        if self.parts_left == 0 {
+            return None;
+        }
+        self.parts_left -= 1;
+        self.points_left = match self.header.geometry_type {
+            GeometryType::Point => 1,
+            _ => self.read_varint()?,
+        };
+        if self.header.geometry_type == GeometryType::MultiPoint && self.has_id_list {
+            self.skip_varints(self.points_left);
+        }
+        Some(())
+    }
+
+    fn next_point(&mut self) -> Option<(f64, f64)> {
+        for i in 0..self.dimensions {
+            let Some(change) = self.read_varint() else {
+                self.points_left = 0;
+                return None;
+            };
+            self.last[i] = self.last[i].wrapping_add(zigzag64_decode(change));
+        }
+        self.points_left -= 1;
+        Some((
+            self.last[1] as f64 / self.scale,
+            self.last[0] as f64 / self.scale,
+        ))
+    }
+
+    fn read_varint(&mut self) -> Option<u64> {
+        let value = varint64_decode7(&mut self.data);
+        if value.is_none() {
+            // Malformed input, stop decoding
+            self.parts_left = 0;
+        }
+        value
+    }
+
+    fn skip_varints(&mut self, count: u64) {
+        for _ in 0..count {
+            if self.read_varint().is_none() {
+                return;
+            }
+        }
+    }
+}
+
+impl Iterator for TwkbIter<'_> {
+    type Item = (f64, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.points_left == 0 {
+            self.start_part()?;
+        }
+        self.next_point()
+    }
+}
+
+/// Iterator over points of a single part of TWKB geometry, created by [`TwkbIter::next_part()`].
+pub struct Part<'p, 'a> {
+    iter: &'p mut TwkbIter<'a>,
+}
+
+impl Part<'_, '_> {
+    /// Number of points left in this part, as stored in TWKB.
+    pub fn len(&self) -> usize {
+        self.iter.points_left as usize
+    }
+
+    /// Checks if there are no points left in this part.
+    pub fn is_empty(&self) -> bool {
+        self.iter.points_left == 0
+    }
+}
+
+impl Iterator for Part<'_, '_> {
+    type Item = (f64, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter.points_left == 0 {
+            return None;
+        }
+        self.iter.next_point()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // The number of points may be wrong only for malformed input.
+        (0, Some(self.len()))
+    }
+}
+
+/// Parses the TWKB header and returns an iterator over the points of the geometry.
+///
+/// ```
+/// use polyline_iter::twkb;
+///
+/// // SELECT ST_AsTWKB('POLYGON((0 0, 0 1, 1 1, 0 0))'::geometry, 0, 0, 0, false, true)
+/// let data = [0x03, 0x01, 0x00, 0x02, 0x00, 0x02, 0x01, 0x04, 0x00, 0x00, 0x00, 0x02, 0x02, 0x00, 0x01, 0x01];
+/// let iter = twkb::decode(&data).unwrap();
+/// let header = iter.header();
+/// assert_eq!(header.geometry_type, twkb::GeometryType::Polygon);
+/// assert_eq!(header.bbox.unwrap().max_lat, 1.0);
+/// assert_eq!(iter.collect::<Vec<_>>(), [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)]);
+/// ```
+pub fn decode(data: &[u8]) -> Result<TwkbIter<'_>, DecodeError> {
+    TwkbIter::new(data)
+}
+
+/// Optional fields written to the TWKB header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EncodeOptions {
+    /// Write the bounding box of the geometry.
+    pub bbox: bool,
+    /// Write the size of the geometry in bytes, which allows to skip it without decoding.
+    pub size: bool,
+}
+
+/// Encodes points as a TWKB LineString with the given precision, from -8 to 7.
+pub fn encode_linestring(
+    precision: i8,
+    points: impl IntoIterator<Item = (f64, f64)>,
+    options: EncodeOptions,
+) -> Vec<u8> {
+    encode_parts(GeometryType::LineString, precision, [points], options)
+}
+
+/// Encodes lines as a TWKB MultiLineString with the given precision, from -8 to 7.
+pub fn encode_multilinestring<L>(
+    precision: i8,
+    lines: impl IntoIterator<Item = L>,
+    options: EncodeOptions,
+) -> Vec<u8>
+where
+    L: IntoIterator<Item = (f64, f64)>,
+{
+    encode_parts(GeometryType::MultiLineString, precision, lines, options)
+}
+
+/// Encodes rings as a TWKB Polygon with the given precision, from -8 to 7.
+/// The first ring is the exterior one, and the rest are holes. Rings are expected to be closed.
+///
+/// ```
+/// use polyline_iter::twkb;
+///
+/// let options = twkb::EncodeOptions { bbox: true, size: false };
+/// let data = twkb::encode_polygon(0, [[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)]], options);
+/// assert_eq!(data, [0x03, 0x01, 0x00, 0x02, 0x00, 0x02, 0x01, 0x04, 0x00, 0x00, 0x00, 0x02, 0x02, 0x00, 0x01, 0x01]);
+/// ```
+pub fn encode_polygon<R>(
+    precision: i8,
+    rings: impl IntoIterator<Item = R>,
+    options: EncodeOptions,
+) -> Vec<u8>
+where
+    R: IntoIterator<Item = (f64, f64)>,
+{
+    encode_parts(GeometryType::Polygon, precision, rings, options)
+}
+
+fn encode_parts<P>(
+    geometry_type: GeometryType,
+    precision: i8,
+    parts: impl IntoIterator<Item = P>,
+    options: EncodeOptions,
+) -> Vec<u8>
+where
+    P: IntoIterator<Item = (f64, f64)>,
+{
+    assert!(
+        (-8..=7).contains(&precision),
+        "TWKB precision must be from -8 to 7"
+    );
+    let scale = 10.0_f64.powi(precision as i32);
+
+    let mut body = Vec::with_capacity(16);
+    let mut part = Vec::with_capacity(16);
+    let mut parts_count = 0;
+    let mut points_count = 0;
+    // min x, min y, max x, max y
+    let mut bbox = [i64::MAX, i64::MAX, i64::MIN, i64::MIN];
+    let mut prev = (0, 0);
+    for points in parts {
+        part.clear();
+        let mut count = 0;
+        for (lat, lon) in points {
+            // Deltas are computed on already scaled values, as the decoder sums up integers.
+            let x = (lon * scale).round() as i64;
+            let y = (lat * scale).round() as i64;
+            varint64_encode7(zigzag64_encode(x - prev.0), &mut part);
+            varint64_encode7(zigzag64_encode(y - prev.1), &mut part);
+            bbox = [
+                bbox[0].min(x),
+                bbox[1].min(y),
+                bbox[2].max(x),
+                bbox[3].max(y),
+            ];
+            prev = (x, y);
+            count += 1;
+        }
+        varint64_encode7(count, &mut body);
+        body.extend_from_slice(&part);
+        parts_count += 1;
+        points_count += count;
+    }
+
+    let mut result = Vec::with_capacity(body.len() + 16);
+    result.push((zigzag64_encode(precision as i64) << 4) as u8 | geometry_type as u8);
+    if points_count == 0 {
+        result.push(IS_EMPTY);
+        return result;
+    }
+    let mut metadata = 0;
+    if options.bbox {
+        metadata |= HAS_BBOX;
+    }
+    if options.size {
+        metadata |= HAS_SIZE;
+    }
+    result.push(metadata);
+
+    let mut rest = Vec::with_capacity(body.len() + 16);
+    if options.bbox {
+        let [min_x, min_y, max_x, max_y] = bbox;
+        varint64_encode7(zigzag64_encode(min_x), &mut rest);
+        varint64_encode7(zigzag64_encode(max_x - min_x), &mut rest);
+        varint64_encode7(zigzag64_encode(min_y), &mut rest);
+        varint64_encode7(zigzag64_encode(max_y - min_y), &mut rest);
+    }
+    if geometry_type != GeometryType::LineString {
+        varint64_encode7(parts_count, &mut rest);
+    }
+    rest.extend_from_slice(&body);
+
+    if options.size {
+        varint64_encode7(rest.len() as u64, &mut result);
+    }
+    result.extend_from_slice(&rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode as decode_polyline;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn postgis_examples() {
+        // SELECT ST_AsTWKB('POINT(1 1)'::geometry)
+        let iter = decode(&[0x01, 0x00, 0x02, 0x02]).unwrap();
+        assert_eq!(iter.header().geometry_type, GeometryType::Point);
+        assert_eq!(iter.collect::<Vec<_>>(), [(1.0, 1.0)]);
+
+        // SELECT ST_AsTWKB('LINESTRING(1 1, 5 5)'::geometry)
+        let data = [0x02, 0x00, 0x02, 0x02, 0x02, 0x08, 0x08];
+        assert_eq!(
+            decode(&data).unwrap().collect::<Vec<_>>(),
+            [(1.0, 1.0), (5.0, 5.0)]
+        );
+        assert_eq!(
+            encode_linestring(0, [(1.0, 1.0), (5.0, 5.0)], EncodeOptions::default()),
+            data
+        );
+
+        // SELECT ST_AsTWKB('LINESTRING EMPTY'::geometry)
+        let data = [0x02, 0x10];
+        assert_eq!(decode(&data).unwrap().next(), None);
+        assert_eq!(encode_linestring(0, [], EncodeOptions::default()), data);
+    }
+
+    #[test]
+    fn header() {
+        let points: Vec<_> =
+            decode_polyline(5, "angrIk~inAgwDybH_|D_{KeoEwtLozFo`Gre@tcA").collect();
+        let options = EncodeOptions {
+            bbox: true,
+            size: true,
+        };
+        for precision in [-2, 0, 5, 7] {
+            let data = encode_linestring(precision, points.iter().copied(), options);
+            let header = decode(&data).unwrap().header();
+            assert_eq!(header.geometry_type, GeometryType::LineString);
+            assert_eq!(header.precision, precision);
+            assert!(!header.has_z && !header.has_m);
+        }
+
+        let data = encode_linestring(5, points.iter().copied(), options);
+        let iter = decode(&data).unwrap();
+        let header = iter.header();
+        // type + metadata + size field
+        assert_eq!(header.size, Some(data.len() as u64 - 3));
+        assert_eq!(
+            header.bbox,
+            Some(BoundingBox {
+                min_lat: 55.58513,
+                min_lon: 12.99958,
+                max_lat: 55.7184,
+                max_lon: 13.22343,
+            })
+        );
+        assert_eq!(iter.collect::<Vec<_>>(), points);
+
+        assert_eq!(decode(&[]).err(), Some(DecodeError::Truncated));
+        assert_eq!(decode(&[0x02]).err(), Some(DecodeError::Truncated));
+        assert_eq!(decode(&[0x02, 0x01]).err(), Some(DecodeError::Truncated));
+        assert_eq!(
+            decode(&[0x06, 0x00]).err(),
+            Some(DecodeError::UnsupportedType(6))
+        );
+    }
+
+    #[test]
+    fn multilinestring() {
+        let lines = [
+            vec![(55.58513, 12.99958), (55.61461, 13.04627)],
+            vec![],
+            vec![
+                (55.64485, 13.11219),
+                (55.67816, 13.18223),
+                (55.7184, 13.22343),
+            ],
+        ];
+        let data = encode_multilinestring(5, lines.clone(), EncodeOptions::default());
+        let mut iter = decode(&data).unwrap();
+        assert_eq!(iter.header().geometry_type, GeometryType::MultiLineString);
+        for line in &lines {
+            let part = iter.next_part().unwrap();
+            assert_eq!(part.len(), line.len());
+            assert_eq!(&part.collect::<Vec<_>>(), line);
+        }
+        assert!(iter.next_part().is_none());
+
+        // Flattened points
+        assert_eq!(decode(&data).unwrap().collect::<Vec<_>>(), lines.concat());
+
+        // Partially consumed parts are skipped
+        let mut iter = decode(&data).unwrap();
+        assert_eq!(iter.next_part().unwrap().next(), Some(lines[0][0]));
+        assert!(iter.next_part().unwrap().is_empty());
+        assert_eq!(iter.next_part().unwrap().last(), Some(lines[2][2]));
+    }
+
+    #[test]
+    fn polygon() {
+        let exterior = [
+            (0.0, 0.0),
+            (0.0, 10.0),
+            (10.0, 10.0),
+            (10.0, 0.0),
+            (0.0, 0.0),
+        ];
+        let hole = [(2.0, 2.0), (2.0, 3.0), (3.0, 3.0), (2.0, 2.0)];
+        let options = EncodeOptions {
+            bbox: false,
+            size: true,
+        };
+        let data = encode_polygon(
+            1,
+            [&exterior[..], &hole[..]].map(|ring| ring.iter().copied()),
+            options,
+        );
+        let mut iter = decode(&data).unwrap();
+        assert_eq!(iter.next_part().unwrap().collect::<Vec<_>>(), exterior);
+        assert_eq!(iter.next_part().unwrap().collect::<Vec<_>>(), hole);
+        assert!(iter.next_part().is_none());
+    }
+
+    #[test]
+    fn extended_dimensions() {
+        // SELECT ST_AsTWKB('LINESTRING Z(1 2 3, 4 5 6)'::geometry, 0, 0)
+        let data = [0x02, 0x08, 0x01, 0x02, 0x02, 0x04, 0x06, 0x06, 0x06, 0x06];
+        let iter = decode(&data).unwrap();
+        assert!(iter.header().has_z);
+        assert_eq!(iter.collect::<Vec<_>>(), [(2.0, 1.0), (5.0, 4.0)]);
+    }
+
+    #[test]
+    fn broken_data() {
+        let data = encode_linestring(
+            5,
+            [(55.58513, 12.99958), (55.61461, 13.04627)],
+            EncodeOptions::default(),
+        );
+        let truncated = &data[..data.len() - 1];
+        assert_eq!(
+            decode(truncated).unwrap().collect::<Vec<_>>(),
+            [(55.58513, 12.99958)]
+        );
+        assert_eq!(decode(&[0x02, 0x00, 0xFF]).unwrap().next(), None);
+    }
+}
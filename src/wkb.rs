@@ -0,0 +1,431 @@
+//! Well-Known Binary `LineString` and `MultiLineString` writer and parser, including the PostGIS EWKB extension
+//! with SRID, e.g. for `ST_GeomFromEWKB()` and `ST_AsEWKB()`.
+//!
+//! WKB points are `(x, y)`, which is `(lon, lat)` for geographic coordinates, while the rest of the crate
+//! uses `(lat, lon)` pairs, so the axes are swapped in both directions.
+
+use crate::decode;
+
+const LINESTRING: u32 = 2;
+const MULTILINESTRING: u32 = 5;
+/// EWKB flag of the geometry type indicating that points have Z values.
+const EWKB_Z: u32 = 0x8000_0000;
+/// EWKB flag of the geometry type indicating that points have M values.
+const EWKB_M: u32 = 0x4000_0000;
+/// EWKB flag of the geometry type indicating that SRID follows the type.
+const EWKB_SRID: u32 = 0x2000_0000;
+
+/// Byte order of WKB numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ByteOrder {
+    /// NDR, used by PostGIS on all common platforms.
+    #[default]
+    LittleEndian,
+    /// XDR, the network byte order.
+    BigEndian,
+}
+
+impl ByteOrder {
+    fn u32_bytes(self, value: u32) -> [u8; 4] {
+        match self {
+            ByteOrder::LittleEndian => value.to_le_bytes(),
+            ByteOrder::BigEndian => value.to_be_bytes(),
+        }
+    }
+
+    fn f64_bytes(self, value: f64) -> [u8; 8] {
+        match self {
+            ByteOrder::LittleEndian => value.to_le_bytes(),
+            ByteOrder::BigEndian => value.to_be_bytes(),
+        }
+    }
+}
+
+/// Options for [`to_wkb_linestring()`] and [`to_wkb_multilinestring()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WkbOptions {
+    /// Byte order of every number in the output, little endian by default as most databases write it.
+    pub byte_order: ByteOrder,
+    /// Writes EWKB with the given SRID, e.g. 4326 for WGS 84, instead of plain WKB.
+    pub srid: Option<u32>,
+}
+
+/// Error returned by [`parse_wkb()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WkbError {
+    /// The data ends in the middle of the geometry.
+    Truncated,
+    /// The byte order marker is neither 0 nor 1.
+    InvalidByteOrder(u8),
+    /// The geometry type is neither `LineString` nor `MultiLineString` of `LineString`s.
+    UnsupportedType(u32),
+    /// There are bytes left after the geometry.
+    TrailingBytes,
+}
+
+impl std::fmt::Display for WkbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WkbError::Truncated => write!(f, "WKB is truncated"),
+            WkbError::InvalidByteOrder(byte) => write!(f, "invalid WKB byte order {byte}"),
+            WkbError::UnsupportedType(geometry_type) => {
+                write!(f, "unsupported WKB geometry type {geometry_type:#010x}")
+            }
+            WkbError::TrailingBytes => write!(f, "unexpected bytes after WKB geometry"),
+        }
+    }
+}
+
+impl std::error::Error for WkbError {}
+
+/// Appends a polyline as WKB or EWKB `LineString` to `out`.
+/// The point count is patched in once the polyline is decoded, so a malformed tail is simply left out.
+///
+/// ```
+/// use polyline_iter::{ByteOrder, WkbOptions};
+///
+/// let polyline = polyline_iter::encode(5, [(2.0, 1.0), (4.0, 3.0)]);
+/// let mut wkb = Vec::new();
+/// let options = WkbOptions { byte_order: ByteOrder::LittleEndian, srid: Some(4326) };
+/// polyline_iter::to_wkb_linestring(5, options, &polyline, &mut wkb);
+/// // `SELECT ST_AsEWKB('SRID=4326;LINESTRING(1 2,3 4)')`
+/// assert_eq!(wkb[..13], [0x01, 0x02, 0x00, 0x00, 0x20, 0xE6, 0x10, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00]);
+/// assert_eq!(polyline_iter::parse_wkb(&wkb), Ok(vec![vec![(2.0, 1.0), (4.0, 3.0)]]));
+/// ```
+pub fn to_wkb_linestring(precision: u8, options: WkbOptions, polyline: &str, out: &mut Vec<u8>) {
+    write_header(options, LINESTRING, out);
+    write_points(precision, options.byte_order, polyline, out);
+}
+
+/// Appends polylines as WKB or EWKB `MultiLineString` to `out`.
+/// The SRID, if any, is written only once for the whole geometry, as PostGIS does.
+///
+/// ```
+/// use polyline_iter::WkbOptions;
+///
+/// let mut wkb = Vec::new();
+/// polyline_iter::to_wkb_multilinestring(5, WkbOptions::default(), ["angrIk~inA", "gwDybH"], &mut wkb);
+/// assert_eq!(
+///     polyline_iter::parse_wkb(&wkb),
+///     Ok(vec![vec![(55.58513, 12.99958)], vec![(0.02948, 0.04669)]])
+/// );
+/// ```
+pub fn to_wkb_multilinestring<'a>(
+    precision: u8,
+    options: WkbOptions,
+    polylines: impl IntoIterator<Item = &'a str>,
+    out: &mut Vec<u8>,
+) {
+    let byte_order = options.byte_order;
+    write_header(options, MULTILINESTRING, out);
+    let count_offset = out.len();
+    out.extend_from_slice(&[0; 4]);
+    let mut count = 0;
+    for polyline in polylines {
+        let options = WkbOptions {
+            byte_order,
+            srid: None,
+        };
+        write_header(options, LINESTRING, out);
+        write_points(precision, byte_order, polyline, out);
+        count += 1;
+    }
+    out[count_offset..count_offset + 4].copy_from_slice(&byte_order.u32_bytes(count));
+}
+
+fn write_header(options: WkbOptions, geometry_type: u32, out: &mut Vec<u8>) {
+    let byte_order = options.byte_order;
+    out.push(match byte_order {
+        ByteOrder::BigEndian => 0,
+        ByteOrder::LittleEndian => 1,
+    });
+    match options.srid {
+        Some(srid) => {
+            out.extend_from_slice(&byte_order.u32_bytes(geometry_type | EWKB_SRID));
+            out.extend_from_slice(&byte_order.u32_bytes(srid));
+        }
+        None => out.extend_from_slice(&byte_order.u32_bytes(geometry_type)),
+    }
+}
+
+/// Writes the number of points followed by `(x, y)` pairs.
+fn write_points(precision: u8, byte_order: ByteOrder, polyline: &str, out: &mut Vec<u8>) {
+    // The number of points is patched afterwards, so the polyline is decoded only once
+    let count_offset = out.len();
+    out.extend_from_slice(&[0; 4]);
+    let mut count = 0;
+    for (lat, lon) in decode(precision, polyline) {
+        out.extend_from_slice(&byte_order.f64_bytes(lon));
+        out.extend_from_slice(&byte_order.f64_bytes(lat));
+        count += 1;
+    }
+    out[count_offset..count_offset + 4].copy_from_slice(&byte_order.u32_bytes(count));
+}
+
+/// Parses WKB or EWKB `LineString` or `MultiLineString` into lines of `(lat, lon)` pairs, ready for
+/// [`encode()`](crate::encode). `LineString` always produces a single line. Both byte orders are supported,
+/// as well as Z and M values in EWKB and ISO flavors, which are skipped. SRID is skipped as well.
+///
+/// ```
+/// // `SELECT ST_AsBinary('LINESTRING(1 2,3 4)'::geometry, 'XDR')`
+/// let wkb = [
+///     0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02,
+///     0x3F, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+///     0x40, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+/// ];
+/// let lines = polyline_iter::parse_wkb(&wkb).unwrap();
+/// assert_eq!(lines, [vec![(2.0, 1.0), (4.0, 3.0)]]);
+/// let binary = polyline_iter::encode_binary(5, lines[0].iter().copied());
+/// assert!(polyline_iter::decode_binary(5, &binary).eq(lines[0].iter().copied()));
+/// ```
+pub fn parse_wkb(wkb: &[u8]) -> Result<Vec<Vec<(f64, f64)>>, WkbError> {
+    let mut reader = Reader {
+        wkb,
+        byte_order: ByteOrder::LittleEndian,
+    };
+    let mut lines = Vec::new();
+    reader.geometry(&mut lines, false)?;
+    if !reader.wkb.is_empty() {
+        return Err(WkbError::TrailingBytes);
+    }
+    Ok(lines)
+}
+
+struct Reader<'a> {
+    wkb: &'a [u8],
+    /// Byte order of the current geometry, as nested geometries may have their own.
+    byte_order: ByteOrder,
+}
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], WkbError> {
+        let (bytes, rest) = self.wkb.split_first_chunk().ok_or(WkbError::Truncated)?;
+        self.wkb = rest;
+        Ok(*bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, WkbError> {
+        let bytes = self.bytes()?;
+        Ok(match self.byte_order {
+            ByteOrder::LittleEndian => u32::from_le_bytes(bytes),
+            ByteOrder::BigEndian => u32::from_be_bytes(bytes),
+        })
+    }
+
+    fn f64(&mut self) -> Result<f64, WkbError> {
+        let bytes = self.bytes()?;
+        Ok(match self.byte_order {
+            ByteOrder::LittleEndian => f64::from_le_bytes(bytes),
+            ByteOrder::BigEndian => f64::from_be_bytes(bytes),
+        })
+    }
+
+    /// Reads a geometry with its header and appends its lines.
+    fn geometry(&mut self, lines: &mut Vec<Vec<(f64, f64)>>, nested: bool) -> Result<(), WkbError> {
+        self.byte_order = match self.bytes::<1>()?[0] {
+            0 => ByteOrder::BigEndian,
+            1 => ByteOrder::LittleEndian,
+            byte => return Err(WkbError::InvalidByteOrder(byte)),
+        };
+        let raw_type = self.u32()?;
+        if raw_type & EWKB_SRID != 0 {
+            self.u32()?;
+        }
+        // EWKB sets the flags, while ISO adds 1000 for Z, 2000 for M and 3000 for ZM
+        let iso_type = raw_type & !(EWKB_Z | EWKB_M | EWKB_SRID);
+        let dimensions = 2
+            + (raw_type & EWKB_Z != 0) as usize
+            + (raw_type & EWKB_M != 0) as usize
+            + match iso_type / 1000 {
+                0 => 0,
+                1 | 2 => 1,
+                3 => 2,
+                _ => return Err(WkbError::UnsupportedType(raw_type)),
+            };
+        match iso_type % 1000 {
+            LINESTRING => lines.push(self.points(dimensions)?),
+            MULTILINESTRING if !nested => {
+                for _ in 0..self.u32()? {
+                    self.geometry(lines, true)?;
+                }
+            }
+            _ => return Err(WkbError::UnsupportedType(raw_type)),
+        }
+        Ok(())
+    }
+
+    fn points(&mut self, dimensions: usize) -> Result<Vec<(f64, f64)>, WkbError> {
+        let count = self.u32()? as usize;
+        // The count is not trusted for the allocation, as it may be malformed
+        let mut points = Vec::with_capacity(count.min(self.wkb.len() / (8 * dimensions)));
+        for _ in 0..count {
+            let lon = self.f64()?;
+            let lat = self.f64()?;
+            for _ in 2..dimensions {
+                self.f64()?;
+            }
+            points.push((lat, lon));
+        }
+        Ok(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode;
+    use crate::test_data::{EDGE_LINES6, edge_polylines6};
+    use pretty_assertions::assert_eq;
+
+    fn hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn linestring(options: WkbOptions, points: &[(f64, f64)]) -> Vec<u8> {
+        let mut wkb = Vec::new();
+        to_wkb_linestring(5, options, &encode(5, points.iter().copied()), &mut wkb);
+        wkb
+    }
+
+    #[test]
+    fn postgis_compatibility() {
+        let points = [(2.0, 1.0), (4.0, 3.0)];
+        // Outputs of `ST_AsEWKB()` and `ST_AsBinary()` for 'SRID=4326;LINESTRING(1 2,3 4)'
+        let golden = [
+            (
+                ByteOrder::LittleEndian,
+                Some(4326),
+                "0102000020E610000002000000000000000000F03F000000000000004000000000000008400000000000001040",
+            ),
+            (
+                ByteOrder::BigEndian,
+                Some(4326),
+                "0020000002000010E6000000023FF0000000000000400000000000000040080000000000004010000000000000",
+            ),
+            (
+                ByteOrder::LittleEndian,
+                None,
+                "010200000002000000000000000000F03F000000000000004000000000000008400000000000001040",
+            ),
+            (
+                ByteOrder::BigEndian,
+                None,
+                "0000000002000000023FF0000000000000400000000000000040080000000000004010000000000000",
+            ),
+        ];
+        for (byte_order, srid, expected) in golden {
+            let wkb = linestring(WkbOptions { byte_order, srid }, &points);
+            assert_eq!(wkb, hex(expected));
+            assert_eq!(parse_wkb(&wkb), Ok(vec![points.to_vec()]));
+        }
+
+        // 'SRID=4326;MULTILINESTRING((1 2,3 4),(5 6,7 8))'
+        let expected = hex(concat!(
+            "0105000020E610000002000000",
+            "010200000002000000000000000000F03F000000000000004000000000000008400000000000001040",
+            "010200000002000000000000000000144000000000000018400000000000001C400000000000002040",
+        ));
+        let polylines = [
+            encode(5, [(2.0, 1.0), (4.0, 3.0)]),
+            encode(5, [(6.0, 5.0), (8.0, 7.0)]),
+        ];
+        let mut wkb = Vec::new();
+        let options = WkbOptions {
+            byte_order: ByteOrder::LittleEndian,
+            srid: Some(4326),
+        };
+        to_wkb_multilinestring(5, options, polylines.iter().map(String::as_str), &mut wkb);
+        assert_eq!(wkb, expected);
+        assert_eq!(
+            parse_wkb(&wkb),
+            Ok(vec![
+                vec![(2.0, 1.0), (4.0, 3.0)],
+                vec![(6.0, 5.0), (8.0, 7.0)]
+            ])
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let polylines = edge_polylines6();
+        for byte_order in [ByteOrder::LittleEndian, ByteOrder::BigEndian] {
+            for srid in [None, Some(4326)] {
+                let options = WkbOptions { byte_order, srid };
+                let mut wkb = Vec::new();
+                to_wkb_multilinestring(6, options, polylines.iter().map(String::as_str), &mut wkb);
+                assert_eq!(parse_wkb(&wkb).unwrap(), EDGE_LINES6);
+                // Nested line strings never repeat the SRID
+                let srid_len = if srid.is_some() { 4 } else { 0 };
+                let points = EDGE_LINES6.iter().map(|line| line.len()).sum::<usize>();
+                assert_eq!(wkb.len(), 9 + srid_len + 3 * 9 + points * 16);
+
+                let mut wkb = Vec::new();
+                to_wkb_multilinestring(6, options, [], &mut wkb);
+                assert_eq!(parse_wkb(&wkb), Ok(vec![]));
+            }
+        }
+    }
+
+    #[test]
+    fn z_and_m() {
+        let expected = Ok(vec![vec![(2.0, 1.0), (4.0, 3.0)]]);
+        // 'LINESTRING Z (1 2 10,3 4 20)' as ISO WKB and EWKB
+        assert_eq!(
+            parse_wkb(&hex(concat!(
+                "01EA03000002000000",
+                "000000000000F03F00000000000000400000000000002440",
+                "000000000000084000000000000010400000000000003440",
+            ))),
+            expected
+        );
+        assert_eq!(
+            parse_wkb(&hex(concat!(
+                "010200008002000000",
+                "000000000000F03F00000000000000400000000000002440",
+                "000000000000084000000000000010400000000000003440",
+            ))),
+            expected
+        );
+        // 'SRID=4326;LINESTRING ZM (1 2 10 100,3 4 20 200)' as EWKB
+        assert_eq!(
+            parse_wkb(&hex(concat!(
+                "01020000E0E610000002000000",
+                "000000000000F03F000000000000004000000000000024400000000000005940",
+                "0000000000000840000000000000104000000000000034400000000000006940",
+            ))),
+            expected
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let wkb = linestring(WkbOptions::default(), &[(2.0, 1.0), (4.0, 3.0)]);
+        for len in 0..wkb.len() {
+            assert_eq!(parse_wkb(&wkb[..len]), Err(WkbError::Truncated));
+        }
+        let trailing = [wkb.as_slice(), &[0xDE, 0xAD]].concat();
+        assert_eq!(parse_wkb(&trailing), Err(WkbError::TrailingBytes));
+        assert_eq!(
+            parse_wkb(&[2, 2, 0, 0, 0]),
+            Err(WkbError::InvalidByteOrder(2))
+        );
+        // POINT(1 2)
+        assert_eq!(
+            parse_wkb(&hex("0101000000000000000000F03F0000000000000040")),
+            Err(WkbError::UnsupportedType(1))
+        );
+        // MULTILINESTRING inside of MULTILINESTRING
+        assert_eq!(
+            parse_wkb(&hex("010500000001000000010500000000000000")),
+            Err(WkbError::UnsupportedType(5))
+        );
+        // Huge count without points
+        assert_eq!(
+            parse_wkb(&hex("0102000000FFFFFFFF")),
+            Err(WkbError::Truncated)
+        );
+    }
+}
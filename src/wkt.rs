@@ -0,0 +1,374 @@
+//! Well-Known Text `LINESTRING` and `MULTILINESTRING` writer and parser, e.g. for PostGIS `ST_GeomFromText()`.
+//!
+//! WKT points are `x y`, which is `lon lat` for geographic coordinates, while the rest of the crate
+//! uses `(lat, lon)` pairs, so the axes are swapped in both directions.
+
+use std::fmt;
+
+use crate::{FloatFormat, decode};
+
+/// Error returned by [`parse_wkt()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WktError {
+    /// Malformed WKT or a point with less than 2 or more than 4 numbers, at the given byte offset.
+    Syntax { offset: usize },
+    /// The geometry is neither `LINESTRING` nor `MULTILINESTRING`.
+    UnsupportedType,
+}
+
+impl fmt::Display for WktError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WktError::Syntax { offset } => write!(f, "invalid WKT at offset {offset}"),
+            WktError::UnsupportedType => {
+                write!(f, "WKT geometry is neither LINESTRING nor MULTILINESTRING")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WktError {}
+
+/// Writes a polyline as WKT `LINESTRING`, formatting coordinates with [`FloatFormat::Shortest`].
+/// A malformed polyline is truncated the same way as in [`to_geojson_linestring`](crate::to_geojson_linestring).
+///
+/// ```
+/// let mut wkt = String::new();
+/// polyline_iter::to_wkt_linestring(5, "angrIk~inAgwDybH", &mut wkt).unwrap();
+/// assert_eq!(wkt, "LINESTRING(12.99958 55.58513,13.04627 55.61461)");
+/// ```
+pub fn to_wkt_linestring(precision: u8, polyline: &str, out: &mut impl fmt::Write) -> fmt::Result {
+    to_wkt_linestring_with(precision, FloatFormat::Shortest, polyline, out)
+}
+
+/// Like [`to_wkt_linestring()`], but with the explicit coordinates format.
+pub fn to_wkt_linestring_with(
+    precision: u8,
+    format: FloatFormat,
+    polyline: &str,
+    out: &mut impl fmt::Write,
+) -> fmt::Result {
+    out.write_str("LINESTRING")?;
+    write_points(precision, format, polyline, " EMPTY", out)
+}
+
+/// Writes polylines as WKT `MULTILINESTRING`, formatting coordinates with [`FloatFormat::Shortest`].
+///
+/// ```
+/// let mut wkt = String::new();
+/// polyline_iter::to_wkt_multilinestring(5, ["angrIk~inAgwDybH", "", "_ibE_ibE"], &mut wkt).unwrap();
+/// assert_eq!(wkt, "MULTILINESTRING((12.99958 55.58513,13.04627 55.61461),EMPTY,(1 1))");
+/// ```
+pub fn to_wkt_multilinestring<'a>(
+    precision: u8,
+    polylines: impl IntoIterator<Item = &'a str>,
+    out: &mut impl fmt::Write,
+) -> fmt::Result {
+    to_wkt_multilinestring_with(precision, FloatFormat::Shortest, polylines, out)
+}
+
+/// Like [`to_wkt_multilinestring()`], but with the explicit coordinates format.
+pub fn to_wkt_multilinestring_with<'a>(
+    precision: u8,
+    format: FloatFormat,
+    polylines: impl IntoIterator<Item = &'a str>,
+    out: &mut impl fmt::Write,
+) -> fmt::Result {
+    out.write_str("MULTILINESTRING")?;
+    let mut polylines = polylines.into_iter().peekable();
+    if polylines.peek().is_none() {
+        return out.write_str(" EMPTY");
+    }
+    for (i, polyline) in polylines.enumerate() {
+        out.write_char(if i == 0 { '(' } else { ',' })?;
+        write_points(precision, format, polyline, "EMPTY", out)?;
+    }
+    out.write_char(')')
+}
+
+/// Writes `(x y,x y)`, or `empty_str` if there are no points.
+fn write_points(
+    precision: u8,
+    format: FloatFormat,
+    polyline: &str,
+    empty_str: &str,
+    out: &mut impl fmt::Write,
+) -> fmt::Result {
+    let mut empty = true;
+    for (lat, lon) in decode(precision, polyline) {
+        out.write_char(if empty { '(' } else { ',' })?;
+        format.write(lon, out)?;
+        out.write_char(' ')?;
+        format.write(lat, out)?;
+        empty = false;
+    }
+    out.write_str(if empty { empty_str } else { ")" })
+}
+
+/// Parses WKT `LINESTRING` or `MULTILINESTRING` into lines of `(lat, lon)` pairs, ready for
+/// [`encode()`](crate::encode). `LINESTRING` always produces a single line. Keywords are case-insensitive,
+/// the EWKT `SRID=...;` prefix is skipped, as well as Z and M values.
+///
+/// ```
+/// let lines = polyline_iter::parse_wkt("SRID=4326;MULTILINESTRING Z((12.99958 55.58513 10, 13.04627 55.61461 12), EMPTY)").unwrap();
+/// assert_eq!(lines, [vec![(55.58513, 12.99958), (55.61461, 13.04627)], vec![]]);
+/// assert_eq!(polyline_iter::encode(5, lines[0].iter().copied()), "angrIk~inAgwDybH");
+/// ```
+pub fn parse_wkt(wkt: &str) -> Result<Vec<Vec<(f64, f64)>>, WktError> {
+    let mut parser = Parser {
+        wkt: wkt.as_bytes(),
+        offset: 0,
+    };
+    if parser.keyword().eq_ignore_ascii_case(b"SRID") {
+        parser.expect(b'=')?;
+        parser.number()?;
+        parser.expect(b';')?;
+    } else {
+        parser.offset = 0;
+    }
+
+    let geometry_type = parser.keyword();
+    let multi = if geometry_type.eq_ignore_ascii_case(b"LINESTRING") {
+        false
+    } else if geometry_type.eq_ignore_ascii_case(b"MULTILINESTRING") {
+        true
+    } else {
+        return Err(WktError::UnsupportedType);
+    };
+    // Extra values are skipped by counting numbers of each point, so the Z/M/ZM tag is not needed
+    let start = parser.offset;
+    let tag = parser.keyword();
+    let tags: [&[u8]; 3] = [b"Z", b"M", b"ZM"];
+    if !tags.iter().any(|known| tag.eq_ignore_ascii_case(known)) {
+        parser.offset = start;
+    }
+
+    let lines = if multi {
+        let mut lines = Vec::new();
+        if !parser.empty() {
+            parser.expect(b'(')?;
+            loop {
+                lines.push(parser.points()?);
+                if !parser.consume(b',') {
+                    break;
+                }
+            }
+            parser.expect(b')')?;
+        }
+        lines
+    } else {
+        vec![parser.points()?]
+    };
+    parser.skip_whitespace();
+    if parser.offset != parser.wkt.len() {
+        return Err(parser.error());
+    }
+    Ok(lines)
+}
+
+struct Parser<'a> {
+    wkt: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self) -> WktError {
+        WktError::Syntax {
+            offset: self.offset,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.wkt.get(self.offset).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|byte| byte.is_ascii_whitespace()) {
+            self.offset += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), WktError> {
+        if !self.consume(byte) {
+            return Err(self.error());
+        }
+        Ok(())
+    }
+
+    fn consume(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        let found = self.peek() == Some(byte);
+        if found {
+            self.offset += 1;
+        }
+        found
+    }
+
+    /// Skips whitespace and returns the following letters, which may be empty.
+    fn keyword(&mut self) -> &'a [u8] {
+        self.skip_whitespace();
+        let start = self.offset;
+        while self.peek().is_some_and(|byte| byte.is_ascii_alphabetic()) {
+            self.offset += 1;
+        }
+        &self.wkt[start..self.offset]
+    }
+
+    /// Consumes the `EMPTY` keyword if it is the next one.
+    fn empty(&mut self) -> bool {
+        let start = self.offset;
+        let found = self.keyword().eq_ignore_ascii_case(b"EMPTY");
+        if !found {
+            self.offset = start;
+        }
+        found
+    }
+
+    fn number(&mut self) -> Result<f64, WktError> {
+        self.skip_whitespace();
+        let start = self.offset;
+        while let Some(b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E') = self.peek() {
+            self.offset += 1;
+        }
+        std::str::from_utf8(&self.wkt[start..self.offset])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .ok_or(WktError::Syntax { offset: start })
+    }
+
+    /// Parses `EMPTY` or `(x y, x y z, ...)`.
+    fn points(&mut self) -> Result<Vec<(f64, f64)>, WktError> {
+        let mut points = Vec::new();
+        if self.empty() {
+            return Ok(points);
+        }
+        self.expect(b'(')?;
+        loop {
+            let lon = self.number()?;
+            let lat = self.number()?;
+            // Optional Z and M
+            for _ in 0..2 {
+                self.skip_whitespace();
+                if !matches!(self.peek(), Some(b',' | b')')) {
+                    self.number()?;
+                }
+            }
+            points.push((lat, lon));
+            if !self.consume(b',') {
+                break;
+            }
+        }
+        self.expect(b')')?;
+        Ok(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode;
+    use crate::test_data::{EDGE_LINES6, edge_polylines6};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn round_trip() {
+        let polylines = edge_polylines6();
+        for format in [FloatFormat::Shortest, FloatFormat::Fixed(6)] {
+            let mut wkt = String::new();
+            to_wkt_multilinestring_with(6, format, polylines.iter().map(String::as_str), &mut wkt)
+                .unwrap();
+            // The empty line keeps its place in the collection
+            assert!(wkt.contains(",EMPTY,"), "{wkt}");
+            assert_eq!(parse_wkt(&wkt).unwrap(), EDGE_LINES6);
+
+            for (line, polyline) in EDGE_LINES6.iter().zip(&polylines) {
+                let mut wkt = String::new();
+                to_wkt_linestring_with(6, format, polyline, &mut wkt).unwrap();
+                assert_eq!(parse_wkt(&wkt).unwrap(), [*line]);
+            }
+        }
+    }
+
+    #[test]
+    fn writer() {
+        let polyline = encode(5, [(-33.86785, 151.20699), (90.0, -180.0)]);
+        let mut wkt = String::new();
+        to_wkt_linestring(5, &polyline, &mut wkt).unwrap();
+        assert_eq!(wkt, "LINESTRING(151.20699 -33.86785,-180 90)");
+
+        let mut wkt = String::new();
+        to_wkt_linestring_with(5, FloatFormat::Fixed(1), &polyline, &mut wkt).unwrap();
+        assert_eq!(wkt, "LINESTRING(151.2 -33.9,-180.0 90.0)");
+
+        let mut wkt = String::new();
+        to_wkt_linestring(5, "", &mut wkt).unwrap();
+        assert_eq!(wkt, "LINESTRING EMPTY");
+
+        let mut wkt = String::new();
+        to_wkt_multilinestring(5, [], &mut wkt).unwrap();
+        assert_eq!(wkt, "MULTILINESTRING EMPTY");
+    }
+
+    #[test]
+    fn parser() {
+        let expected = vec![vec![(2.0, 1.0), (4.0, 3.0)]];
+        assert_eq!(parse_wkt("LINESTRING(1 2,3 4)"), Ok(expected.clone()));
+        assert_eq!(
+            parse_wkt("  linestring ( 1 2 , 3.0 4e0 )  "),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            parse_wkt("LINESTRING ZM (1 2 5 6, 3 4 7 8)"),
+            Ok(expected.clone())
+        );
+        assert_eq!(parse_wkt("LINESTRING (1 2 5, 3 4 7)"), Ok(expected.clone()));
+        assert_eq!(
+            parse_wkt("srid=4326; LINESTRING M(1 2 0,3 4 0)"),
+            Ok(expected)
+        );
+        assert_eq!(parse_wkt("LINESTRING EMPTY"), Ok(vec![vec![]]));
+        assert_eq!(parse_wkt("MULTILINESTRING EMPTY"), Ok(vec![]));
+        assert_eq!(
+            parse_wkt("MULTILINESTRING((1 2),EMPTY,(-3 -4,5 6))"),
+            Ok(vec![
+                vec![(2.0, 1.0)],
+                vec![],
+                vec![(-4.0, -3.0), (6.0, 5.0)]
+            ])
+        );
+    }
+
+    #[test]
+    fn parser_errors() {
+        assert_eq!(parse_wkt("POINT(1 2)"), Err(WktError::UnsupportedType));
+        assert_eq!(parse_wkt(""), Err(WktError::UnsupportedType));
+        assert_eq!(
+            parse_wkt("LINESTRING(1)"),
+            Err(WktError::Syntax { offset: 12 })
+        );
+        assert_eq!(
+            parse_wkt("LINESTRING(1 2 3 4 5)"),
+            Err(WktError::Syntax { offset: 19 })
+        );
+        assert_eq!(
+            parse_wkt("LINESTRING(1 2,)"),
+            Err(WktError::Syntax { offset: 15 })
+        );
+        assert_eq!(
+            parse_wkt("LINESTRING(1 2"),
+            Err(WktError::Syntax { offset: 14 })
+        );
+        assert_eq!(
+            parse_wkt("LINESTRING(1 2) x"),
+            Err(WktError::Syntax { offset: 16 })
+        );
+        assert_eq!(
+            parse_wkt("MULTILINESTRING(1 2)"),
+            Err(WktError::Syntax { offset: 16 })
+        );
+        assert_eq!(
+            parse_wkt("SRID=4326 LINESTRING(1 2)"),
+            Err(WktError::Syntax { offset: 10 })
+        );
+    }
+}